@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+#[derive(Component, Reflect, Debug)]
+pub struct Health {
+  /// 現在の体力
+  pub current: f32,
+  /// 体力の最大値
+  pub max: f32,
+}
+
+impl Health {
+  pub fn new(max: f32) -> Self {
+    Self { current: max, max }
+  }
+}
+
+#[derive(Event, Debug)]
+pub struct DamageEvent {
+  /// ダメージを受けるエンティティ
+  pub target: Entity,
+  pub amount: f32,
+}
+
+pub(super) fn update_damage(
+  mut damage_event_reader: EventReader<DamageEvent>,
+  mut health_query: Query<&mut Health>,
+) {
+  for event in damage_event_reader.read() {
+    if let Ok(mut health) = health_query.get_mut(event.target) {
+      health.current = (health.current - event.amount).clamp(0.0, health.max);
+    }
+  }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use super::{update_damage, DamageEvent, Health};
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_event::<DamageEvent>()
+      .add_systems(Update, update_damage)
+      .register_type::<Health>();
+  }
+}
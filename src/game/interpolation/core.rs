@@ -0,0 +1,79 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// `FixedUpdate`で動かすエンティティの描画上の位置をステップの間で補間する
+/// 向きはマウスで毎フレーム変わるので補間しない
+#[derive(Component, Reflect, Debug)]
+pub struct TranslationInterpolation {
+  /// 1つ前のステップの終わりの位置
+  pub previous: Vec3,
+  /// 最後のステップの終わりの位置
+  pub current: Vec3,
+}
+
+impl TranslationInterpolation {
+  pub fn new(translation: Vec3) -> Self {
+    Self {
+      previous: translation,
+      current: translation,
+    }
+  }
+}
+
+/// 補間していない位置で子のエンティティの位置と向きを求める
+/// `GlobalTransform`は補間した描画上の位置なので、`FixedUpdate`ではこれを使う
+#[derive(SystemParam)]
+pub struct SimulationTransforms<'w, 's> {
+  query: Query<
+    'w,
+    's,
+    (
+      &'static Transform,
+      Option<&'static Parent>,
+      Option<&'static TranslationInterpolation>,
+    ),
+  >,
+}
+
+impl SimulationTransforms<'_, '_> {
+  /// 親をたどってワールド座標の`Transform`を組み立てる
+  /// 補間しているエンティティは最後のステップの終わりの位置を使う
+  pub fn get(&self, entity: Entity) -> Option<Transform> {
+    let mut result = Transform::IDENTITY;
+    let mut next = Some(entity);
+    while let Some(entity) = next {
+      let (transform, parent, interpolation) = self.query.get(entity).ok()?;
+      let mut transform = *transform;
+      if let Some(interpolation) = interpolation {
+        transform.translation = interpolation.current;
+      }
+      result = transform.mul_transform(result);
+      next = parent.map(Parent::get);
+    }
+    Some(result)
+  }
+}
+
+// 補間した位置をステップの終わりの位置に戻してからステップを始める
+pub(super) fn restore_translation(mut query: Query<(&TranslationInterpolation, &mut Transform)>) {
+  for (interpolation, mut transform) in query.iter_mut() {
+    transform.translation = interpolation.current;
+  }
+}
+
+pub(super) fn store_translation(mut query: Query<(&mut TranslationInterpolation, &Transform)>) {
+  for (mut interpolation, transform) in query.iter_mut() {
+    interpolation.previous = interpolation.current;
+    interpolation.current = transform.translation;
+  }
+}
+
+// 次のステップまでに進んだ時間の割合で補間する
+pub(super) fn interpolate_translation(
+  time: Res<Time<Fixed>>,
+  mut query: Query<(&TranslationInterpolation, &mut Transform)>,
+) {
+  let fraction = time.overstep_fraction();
+  for (interpolation, mut transform) in query.iter_mut() {
+    transform.translation = interpolation.previous.lerp(interpolation.current, fraction);
+  }
+}
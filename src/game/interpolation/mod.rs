@@ -0,0 +1,5 @@
+pub(super) mod core;
+pub(super) mod plugin;
+
+pub use core::*;
+pub use plugin::*;
@@ -0,0 +1,20 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+use super::{
+  interpolate_translation, restore_translation, store_translation, TranslationInterpolation,
+};
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+  fn build(&self, app: &mut App) {
+    app
+      .add_systems(FixedFirst, restore_translation)
+      .add_systems(FixedLast, store_translation)
+      .add_systems(
+        PostUpdate,
+        interpolate_translation.before(TransformSystem::TransformPropagate),
+      )
+      .register_type::<TranslationInterpolation>();
+  }
+}
@@ -25,6 +25,18 @@ pub(super) fn update_current_item(
     if keyboard_input.just_pressed(key.item_3) {
       inventory.current_item = if inventory.current_item != 0 { 0 } else { 1 };
     }
+    if keyboard_input.just_pressed(key.item_4) {
+      inventory.current_item = 3;
+    }
+    if keyboard_input.just_pressed(key.item_5) {
+      inventory.current_item = 4;
+    }
+    if keyboard_input.just_pressed(key.item_6) {
+      inventory.current_item = 5;
+    }
+    if keyboard_input.just_pressed(key.item_7) {
+      inventory.current_item = 6;
+    }
 
     if inventory.current_item > inventory.max_count() {
       inventory.current_item = inventory.max_count();
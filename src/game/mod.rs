@@ -1,3 +1,5 @@
+pub(super) mod health;
+pub(super) mod interpolation;
 pub(super) mod inventory;
 pub(super) mod player;
 pub(super) mod plugin;
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::{
+  finished_timer,
+  input::{FixedInput, PlayerInput},
+  stamina::Stamina,
+  Player, PLAYER_OFFSET,
+};
+
+#[derive(Component, Reflect, Debug)]
+pub struct Blink {
+  /// 移動する距離
+  pub distance: f32,
+  /// 連続で使える回数
+  pub max_charges: u32,
+  /// 残りの使用回数
+  pub charges: u32,
+  /// 1回分が回復するまでの時間
+  pub recharge: Timer,
+  /// 移動にかける時間
+  pub active: Timer,
+  /// 移動中の速度
+  pub velocity: Vec3,
+}
+
+impl Default for Blink {
+  fn default() -> Self {
+    Self {
+      distance: 8.0,
+      max_charges: 2,
+      charges: 2,
+      recharge: Timer::from_seconds(2.0, TimerMode::Repeating),
+      active: finished_timer(0.1),
+      velocity: Vec3::ZERO,
+    }
+  }
+}
+
+/// ブリンクを始めたときに送られる
+/// エフェクトや音はこれを読んで再生する
+#[derive(Event, Debug)]
+pub struct BlinkEvent {
+  pub entity: Entity,
+  pub from: Vec3,
+  pub to: Vec3,
+}
+
+type BlinkInputQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static Player,
+    &'static mut Blink,
+    &'static Transform,
+    &'static Collider,
+    Option<&'static mut Stamina>,
+  ),
+>;
+
+// 入力された方向に壁で止まる距離までブリンクを始める
+// `Player::direction`が`update_movement`で消される前に実行する
+pub(super) fn update_blink_input(
+  time: Res<Time>,
+  keyboard_input: Res<FixedInput<KeyCode>>,
+  key: Res<PlayerInput>,
+  rapier_context: Res<RapierContext>,
+  mut blink_event_writer: EventWriter<BlinkEvent>,
+  mut player_query: BlinkInputQuery,
+) {
+  for (entity, player, mut blink, transform, collider, mut stamina) in player_query.iter_mut() {
+    if blink.charges < blink.max_charges {
+      blink.recharge.tick(time.delta());
+      if blink.recharge.just_finished() {
+        blink.charges += 1;
+      }
+    } else {
+      blink.recharge.reset();
+    }
+
+    if !keyboard_input.just_pressed(key.blink) || blink.charges == 0 || !blink.active.finished() {
+      continue;
+    }
+
+    // 入力がなければ前にブリンクする
+    let direction = player
+      .horizontal(
+        player.direction.x * *transform.forward() + player.direction.z * *transform.right(),
+      )
+      .try_normalize()
+      .unwrap_or_else(|| player.horizontal(*transform.forward()).normalize_or_zero());
+
+    if direction == Vec3::ZERO {
+      continue;
+    }
+
+    // 壁を通り抜けないように自分のコライダーで確認する
+    let origin = transform.translation + player.up * PLAYER_OFFSET;
+    let distance = rapier_context
+      .cast_shape(
+        origin,
+        transform.rotation,
+        direction,
+        collider,
+        ShapeCastOptions::with_max_time_of_impact(blink.distance),
+        QueryFilter::default()
+          .exclude_collider(entity)
+          .exclude_sensors(),
+      )
+      .map_or(blink.distance, |(_, hit)| {
+        (hit.time_of_impact - PLAYER_OFFSET).max(0.0)
+      });
+
+    if !stamina
+      .as_deref_mut()
+      .is_none_or(|stamina| stamina.try_spend(stamina.blink_cost))
+    {
+      continue;
+    }
+
+    blink.charges -= 1;
+    blink.velocity = direction * distance / blink.active.duration().as_secs_f32();
+    blink.active.reset();
+
+    blink_event_writer.send(BlinkEvent {
+      entity,
+      from: transform.translation,
+      to: transform.translation + direction * distance,
+    });
+  }
+}
+
+// ブリンク中の移動を`KinematicCharacterController`の移動に加える
+// `update_movement`の後に実行する
+pub(super) fn update_blink(
+  time: Res<Time>,
+  mut player_query: Query<(&mut Blink, &mut KinematicCharacterController)>,
+) {
+  for (mut blink, mut controller) in player_query.iter_mut() {
+    if blink.active.finished() {
+      continue;
+    }
+
+    // 最後のフレームで移動しすぎないようにする
+    let remaining = blink.active.remaining_secs();
+    let step = blink.velocity * time.delta_seconds().min(remaining);
+    blink.active.tick(time.delta());
+
+    controller.translation = Some(controller.translation.unwrap_or_default() + step);
+  }
+}
+
+// エフェクトや音ができるまではログに出して確かめる
+pub(super) fn log_blinks(mut blink_event_reader: EventReader<BlinkEvent>) {
+  for event in blink_event_reader.read() {
+    debug!(
+      "{:?} blinked from {} to {}",
+      event.entity, event.from, event.to
+    );
+  }
+}
@@ -5,32 +5,136 @@ use bevy::{input::mouse::MouseMotion, prelude::*};
 use super::Player;
 
 #[derive(Component, Reflect, Debug)]
-pub(super) struct CameraController {
-  /// カメラの感度
-  pub(super) sensitivity: f32,
+pub struct CameraController {
+  /// カメラの感度(マウスの1カウントあたりに回る角度(度)、左右, 上下)
+  /// 他のゲームの感度に合わせやすいように度で指定する
+  pub sensitivity: Vec2,
+  /// 上下を反転する
+  pub invert_y: bool,
+  /// マウスの動きを滑らかにする時間(秒、0.0で無効)
+  pub smoothing: f32,
+  /// マウスを速く動かしたときに感度を上げる割合(1秒あたりのカウントに掛ける、0.0で無効)
+  pub acceleration: f32,
+  /// 加速で上がる感度の倍率の上限
+  pub acceleration_max: f32,
+  /// 見下ろせる角度
+  pub pitch_min: f32,
+  /// 見上げられる角度
+  pub pitch_max: f32,
+  /// 他のシステムが加えるカメラの傾き(毎フレーム0に戻す)
+  pub(super) roll: f32,
+  /// このフレームで回転した量(左右, 上下)
+  pub(super) look_delta: Vec2,
+  /// 滑らかにしたマウスの速さ(1秒あたりのカウント)
+  pub(super) smoothed_motion: Vec2,
+}
+
+impl Default for CameraController {
+  fn default() -> Self {
+    Self {
+      sensitivity: Vec2::splat(0.0573),
+      invert_y: false,
+      smoothing: 0.0,
+      acceleration: 0.0,
+      acceleration_max: 2.0,
+      pitch_min: -f32::consts::FRAC_PI_2,
+      pitch_max: f32::consts::FRAC_PI_2,
+      roll: 0.0,
+      look_delta: Vec2::ZERO,
+      smoothed_motion: Vec2::ZERO,
+    }
+  }
+}
+
+impl CameraController {
+  /// 1フレーム分のマウスの移動量(カウント)を回転する角度(ラジアン、左右, 上下)にする
+  fn look(&mut self, motion: Vec2, delta: f32) -> Vec2 {
+    let motion = if self.smoothing > 0.0 && delta > 0.0 {
+      // フレームレートで変わらないように速さを滑らかにする
+      let t = 1.0 - (-delta / self.smoothing).exp();
+      self.smoothed_motion = self.smoothed_motion.lerp(motion / delta, t);
+      self.smoothed_motion * delta
+    } else {
+      self.smoothed_motion = Vec2::ZERO;
+      motion
+    };
+
+    let acceleration = if self.acceleration > 0.0 && delta > 0.0 {
+      (1.0 + self.acceleration * motion.length() / delta).min(self.acceleration_max.max(1.0))
+    } else {
+      1.0
+    };
+
+    let invert = if self.invert_y { -1.0 } else { 1.0 };
+    let sensitivity = self.sensitivity * (f32::consts::PI / 180.0);
+    -motion * Vec2::new(1.0, invert) * sensitivity * acceleration
+  }
 }
 
 pub(super) fn update_camera_controller(
+  time: Res<Time>,
   mut mouse_motion: EventReader<MouseMotion>,
   mut player: Query<&mut Transform, With<Player>>,
   mut camera_controller: Query<(&mut CameraController, &mut Transform), Without<Player>>,
 ) {
-  if let (Ok(mut player), Ok((camera_controller, mut camera_controller_transform))) =
+  if let (Ok(mut player), Ok((mut camera_controller, mut camera_controller_transform))) =
     (player.get_single_mut(), camera_controller.get_single_mut())
   {
-    for motion in mouse_motion.read() {
-      // 左右
-      player.rotate_y(-motion.delta.x * camera_controller.sensitivity);
-
-      // 上下
-      camera_controller_transform.rotation = Quat::from_rotation_x(
-        (camera_controller_transform
-          .rotation
-          .to_euler(EulerRot::YXZ)
-          .1
-          - motion.delta.y * camera_controller.sensitivity)
-          .clamp(-f32::consts::FRAC_PI_2, f32::consts::FRAC_PI_2),
-      );
-    }
+    let pitch = camera_controller_transform
+      .rotation
+      .to_euler(EulerRot::YXZ)
+      .1;
+
+    // 滑らかにするときはマウスを止めたフレームでも動かすのでまとめて扱う
+    let motion: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    let look = camera_controller.look(motion, time.delta_seconds());
+
+    // 左右(重力の向きが変わっていても足元を軸に回る)
+    player.rotate_local_y(look.x);
+
+    // 上下
+    let next = (pitch + look.y).clamp(camera_controller.pitch_min, camera_controller.pitch_max);
+    camera_controller.look_delta = Vec2::new(look.x, next - pitch);
+
+    camera_controller_transform.rotation =
+      Quat::from_euler(EulerRot::YXZ, 0.0, next, camera_controller.roll);
+    camera_controller.roll = 0.0;
   };
 }
+
+/// カメラの位置を基準の位置と各システムのずれから組み立てる
+/// 各システムは`Transform`ではなくここに書き、`update_camera_rig`がまとめて`Transform`に書く
+#[derive(Component, Reflect, Debug)]
+pub struct CameraRig {
+  /// プレイヤーから見た立っているときの頭の位置
+  pub base: Vec3,
+  /// しゃがんで下がった高さ(`update_stance`が書く)
+  pub stance: f32,
+  /// 頭の揺れ(`update_view_bob`が書く)
+  pub bob: Vec3,
+  /// 体を傾けたずれ(`update_lean`が書く)
+  pub lean: Vec3,
+}
+
+impl CameraRig {
+  pub fn new(base: Vec3) -> Self {
+    Self {
+      base,
+      stance: 0.0,
+      bob: Vec3::ZERO,
+      lean: Vec3::ZERO,
+    }
+  }
+
+  pub fn translation(&self) -> Vec3 {
+    self.base + Vec3::Y * self.stance + self.bob + self.lean
+  }
+}
+
+// 毎フレーム位置を組み立て直すので、外から`Transform`を書き換えても次のフレームで戻る
+// `update_lean`と`update_view_bob`の後に実行する
+pub(super) fn update_camera_rig(mut camera_query: Query<(&CameraRig, &mut Transform)>) {
+  for (camera_rig, mut transform) in camera_query.iter_mut() {
+    transform.translation = camera_rig.translation();
+  }
+}
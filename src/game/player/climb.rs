@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::world::Ladder;
+
+use super::{camera_controller::CameraController, finished_timer, Player};
+
+#[derive(Component, Reflect, Debug)]
+pub struct Climber {
+  /// 登っているはしご
+  pub ladder: Option<Entity>,
+  /// はしごから離れた後、掴み直せるようになるまでの時間
+  pub detach: Timer,
+  /// この角度より下を見ながら前に進むと降りる(rad)
+  pub look_down_angle: f32,
+  /// ジャンプではしごから離れる速さ
+  pub jump_push: f32,
+}
+
+impl Default for Climber {
+  fn default() -> Self {
+    Self {
+      ladder: None,
+      detach: finished_timer(0.3),
+      look_down_angle: -30_f32.to_radians(),
+      jump_push: 6.0,
+    }
+  }
+}
+
+impl Climber {
+  pub fn climbing(&self) -> bool {
+    self.ladder.is_some()
+  }
+}
+
+// はしごの範囲内では重力を止め、前後の入力で上下に移動する
+// `update_movement_input`の後、`update_movement`の前に実行する
+pub(super) fn update_climb(
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  mut player_query: Query<(Entity, &mut Player, &mut Climber, &Transform, &Collider)>,
+  ladder_query: Query<&Ladder>,
+  camera_query: Query<&Transform, (With<CameraController>, Without<Player>)>,
+) {
+  for (entity, mut player, mut climber, transform, collider) in player_query.iter_mut() {
+    climber.detach.tick(time.delta());
+
+    climber.ladder = None;
+    if climber.detach.finished() {
+      rapier_context.intersections_with_shape(
+        transform.translation,
+        transform.rotation,
+        collider,
+        QueryFilter::default()
+          .exclude_collider(entity)
+          .exclude_solids(),
+        |sensor| {
+          if ladder_query.contains(sensor) {
+            climber.ladder = Some(sensor);
+            false
+          } else {
+            true
+          }
+        },
+      );
+    }
+
+    let Some(ladder) = climber
+      .ladder
+      .and_then(|ladder| ladder_query.get(ladder).ok())
+    else {
+      continue;
+    };
+
+    if !player.jump_buffer.finished() {
+      // 後ろに跳んではしごから離れる(地上からのジャンプとして数える)
+      player.jump_count = 0;
+      player.jump();
+      let push = player.horizontal(*transform.back()) * climber.jump_push;
+      player.inherited_velocity += push;
+
+      climber.ladder = None;
+      climber.detach.reset();
+      continue;
+    }
+
+    // 下を見ているときは前に進むと降りる
+    let pitch = camera_query
+      .get_single()
+      .map_or(0.0, |camera| camera.rotation.to_euler(EulerRot::YXZ).1);
+    let vertical = if pitch < climber.look_down_angle {
+      -player.direction.x
+    } else {
+      player.direction.x
+    };
+
+    // 前後の入力は上下の移動に使う
+    player.direction.x = 0.0;
+    // 重力の倍率は他のシステムと掛け合わせるので0.0を掛けて重力を止める
+    player.gravity_scale *= 0.0;
+    player.set_vertical_velocity(vertical.clamp(-1.0, 1.0) * ladder.climb_speed);
+    player.jump_count = 0;
+  }
+}
@@ -2,12 +2,31 @@ use bevy::{color::palettes::css, core_pipeline::tonemapping::DebandDither, prelu
 use bevy_rapier3d::prelude::*;
 use inventory::Inventory;
 
-use crate::game::shooting::{
-  weapons::{ExampleGun, Mox47},
-  Shooter,
+use crate::game::{
+  health::Health,
+  interpolation::TranslationInterpolation,
+  shooting::{
+    weapons::{ExampleGun, Grenade, Mox47, RocketLauncher, Shotgun, SpreadPattern},
+    Melee, Shooter,
+  },
 };
 
-use super::{camera_controller::CameraController, movement::GroundSensor};
+use super::{
+  blink::Blink,
+  camera_controller::{CameraController, CameraRig},
+  climb::Climber,
+  landing::FallDamage,
+  lean::Lean,
+  locomotion::{Locomotion, LocomotionSettings},
+  momentum::MovementProfile,
+  movement::{AutoStep, GroundSensor, GRAVITY, JUMP_HEIGHT},
+  stamina::Stamina,
+  stance::Stance,
+  swim::Swimmer,
+  trigger::TriggerContacts,
+  view_bob::{ViewBob, WeaponSway},
+  wall_run::WallRun,
+};
 
 pub const PLAYER_HALF_HEIGHT: f32 = 1.0;
 pub const PLAYER_RADIUS: f32 = 0.4;
@@ -25,12 +44,40 @@ pub struct Player {
   pub vertical_accel: f32,
   /// 水平方向の移動速度
   pub horizontal_speed: f32,
+  /// 現在の水平方向の速度(`MovementProfile::Momentum`ではフレームをまたいで保つ)
+  pub horizontal_velocity: Vec3,
   /// 垂直方向の移動速度
   pub vertical_speed: f32,
+  /// 空中で掛かる重力の倍率(壁走りなどで毎フレーム掛け合わせ、`update_movement`で1.0に戻す)
+  pub gravity_scale: f32,
   /// 連続でジャンプできる回数(ダブルジャンプをさせたいなら2)
   pub jump_max_count: u32,
   /// 連続でジャンプする際のクールタイム
   pub jump_cool_time: Timer,
+  /// 最後に接地してからジャンプした回数
+  pub jump_count: u32,
+  /// 足場から離れた後もジャンプできる猶予時間(コヨーテタイム)
+  pub coyote_time: Timer,
+  /// 着地する少し前に押したジャンプを着地時に行う先行入力の時間
+  pub jump_buffer: Timer,
+  /// 足場や壁ジャンプから受け継いだ速度(水平方向は着地するまで保つ)
+  pub inherited_velocity: Vec3,
+  /// 爆発や近接攻撃などで外から受けた水平方向の速度(`add_impulse`で加える)
+  pub external_velocity: Vec3,
+  /// 接地しているときにexternal_velocityを減衰させる割合(1/sec)
+  pub external_friction: f32,
+  /// 空中でexternal_velocityを減衰させる割合(1/sec)
+  pub external_air_friction: f32,
+  /// 受けた衝撃を速度にするときの質量
+  pub mass: f32,
+  /// 水平方向の移動速度の倍率(加速する範囲などで毎フレーム変え、`update_movement`で1.0に戻す)
+  pub speed_scale: f32,
+  /// 上方向(重力の逆向き、`KinematicCharacterController::up`や接地判定に使う)
+  pub up: Vec3,
+  /// 重力の範囲から求めた上方向(`up`はこれに滑らかに近づく)
+  pub target_up: Vec3,
+  /// `up`を`target_up`に合わせる速さ(1/sec)
+  pub up_align_speed: f32,
 }
 
 impl Default for Player {
@@ -39,13 +86,82 @@ impl Default for Player {
       direction: Default::default(),
       vertical_accel: Default::default(),
       horizontal_speed: 1.0,
+      horizontal_velocity: Vec3::ZERO,
       vertical_speed: 1.0,
+      gravity_scale: 1.0,
       jump_max_count: 1,
       jump_cool_time: Default::default(),
+      jump_count: 0,
+      coyote_time: finished_timer(0.12),
+      jump_buffer: finished_timer(0.1),
+      inherited_velocity: Vec3::ZERO,
+      external_velocity: Vec3::ZERO,
+      external_friction: 8.0,
+      external_air_friction: 0.5,
+      mass: 1.0,
+      speed_scale: 1.0,
+      up: Vec3::Y,
+      target_up: Vec3::Y,
+      up_align_speed: 4.0,
+    }
+  }
+}
+
+impl Player {
+  /// 衝撃を加える
+  /// 垂直方向は`vertical_accel`に加えるので接地やジャンプの処理と同じように扱われる
+  pub fn add_impulse(&mut self, impulse: Vec3) {
+    if self.mass <= 0.0 {
+      return;
     }
+
+    let velocity = impulse / self.mass;
+    self.external_velocity += self.horizontal(velocity);
+    self.set_vertical_velocity(self.vertical_velocity() + velocity.dot(self.up));
+  }
+
+  /// 速度を上書きして打ち上げる(ジャンプ台など)
+  pub fn launch(&mut self, velocity: Vec3) {
+    self.external_velocity = self.horizontal(velocity);
+    self.set_vertical_velocity(velocity.dot(self.up));
+  }
+
+  /// 上向きの速度
+  /// `update_movement`では`vertical_accel * 0.2`が下向きの速度になる
+  pub fn vertical_velocity(&self) -> f32 {
+    -self.vertical_accel * 0.2
+  }
+
+  /// 上向きの速度を`vertical_accel`にして設定する
+  pub fn set_vertical_velocity(&mut self, velocity: f32) {
+    self.vertical_accel = -velocity / 0.2;
+  }
+
+  /// ジャンプする
+  /// 重力とJUMP_HEIGHTで打ち消されないように下向きの速度を消してから跳び、
+  /// ジャンプの回数を数えてクールタイムを始め、先行入力を使い切る
+  pub fn jump(&mut self) {
+    self.vertical_accel = self.vertical_accel.clamp(0.0, GRAVITY) + JUMP_HEIGHT;
+    self.jump_count += 1;
+    self.jump_cool_time.reset();
+
+    let duration = self.jump_buffer.duration();
+    self.jump_buffer.tick(duration);
+  }
+
+  /// `up`に垂直な成分(水平方向)
+  pub fn horizontal(&self, vector: Vec3) -> Vec3 {
+    vector.reject_from_normalized(self.up)
   }
 }
 
+/// 既に終わっているTimerを作る
+pub fn finished_timer(seconds: f32) -> Timer {
+  let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+  timer.tick(timer.duration());
+  timer
+}
+
 #[derive(Component, Reflect, Debug, Default)]
 pub(super) struct Body;
 
@@ -68,8 +184,11 @@ pub(super) fn init_player(
         local: Transform::from_xyz(1.0, -1.0, -0.6),
         ..default()
       },
-      Inventory::new(2),
+      Inventory::new(6),
       Shooter::default(),
+      // クイック近接
+      Melee::new(30.0, 20.0, 1.6, 0.2, 0.8),
+      WeaponSway::default(),
     ))
     .with_children(|parent| {
       // TODO: Bundle化する
@@ -113,6 +232,59 @@ pub(super) fn init_player(
         },
         AnimationPlayer::default(),
       ));
+
+      parent.spawn((
+        Name::new("Rocket Launcher"),
+        SpatialBundle::default(),
+        RocketLauncher {
+          cool_time: Timer::from_seconds(1.0, TimerMode::Once),
+          bullet_speed: 40.0,
+          bullet_lifetime: 10.0,
+          explosion_radius: 6.0,
+          explosion_damage: 80.0,
+          explosion_impulse: 60.0,
+        },
+      ));
+
+      parent.spawn((
+        Name::new("Grenade"),
+        SpatialBundle::default(),
+        Grenade {
+          cool_time: Timer::from_seconds(1.0, TimerMode::Once),
+          fuse: 3.0,
+          held: None,
+          min_strength: 8.0,
+          max_strength: 24.0,
+          charge_time: 1.0,
+          radius: 0.15,
+          restitution: 0.5,
+          explosion_radius: 6.0,
+          explosion_damage: 100.0,
+          explosion_impulse: 80.0,
+        },
+      ));
+
+      parent.spawn((
+        Name::new("Knife"),
+        SpatialBundle::default(),
+        Melee::new(60.0, 10.0, 2.0, 0.25, 0.5),
+      ));
+
+      parent.spawn((
+        Name::new("Shotgun"),
+        SpatialBundle::default(),
+        Shotgun {
+          cool_time: Timer::from_seconds(0.8, TimerMode::Once),
+          pellets: 8,
+          spread: SpreadPattern::Cone(6_f32.to_radians()),
+          pellet_damage: 12.0,
+          hitscan: false,
+          range: 40.0,
+          bullet_speed: 100.0,
+          bullet_lifetime: 1.0,
+          seed: 0x2545_f491,
+        },
+      ));
     })
     .id();
 
@@ -122,9 +294,12 @@ pub(super) fn init_player(
       Player {
         horizontal_speed: 8.0,
         vertical_speed: 18.0,
-        jump_cool_time: Timer::from_seconds(1.2, TimerMode::Once),
+        jump_max_count: 2,
+        jump_cool_time: Timer::from_seconds(0.3, TimerMode::Once),
+        mass: 4.0,
         ..default()
       },
+      Health::new(100.0),
       Collider::capsule_y(PLAYER_HALF_HEIGHT, PLAYER_RADIUS),
       PbrBundle {
         mesh: meshes.add(Capsule3d::new(PLAYER_RADIUS, PLAYER_HALF_HEIGHT * 2.0)),
@@ -132,6 +307,7 @@ pub(super) fn init_player(
         transform: Transform::from_xyz(0.0, 1.5, 0.0),
         ..default()
       },
+      TranslationInterpolation::new(Vec3::new(0.0, 1.5, 0.0)),
       RigidBody::KinematicVelocityBased,
       KinematicCharacterController {
         up: Vec3::Y,
@@ -142,6 +318,20 @@ pub(super) fn init_player(
         ..default()
       },
       GroundSensor::default(),
+      Locomotion::default(),
+      LocomotionSettings::default(),
+      Blink::default(),
+      Stance::default(),
+      (
+        WallRun::default(),
+        Climber::default(),
+        Swimmer::default(),
+        FallDamage::default(),
+        MovementProfile::default(),
+        Stamina::default(),
+        TriggerContacts::default(),
+        AutoStep::default(),
+      ),
     ))
     .id();
 
@@ -162,23 +352,23 @@ pub(super) fn init_player(
         transform: Transform::from_xyz(0.0, 1.8, 0.0),
         ..default()
       },
-      CameraController { sensitivity: 0.001 },
+      CameraController::default(),
+      CameraRig::new(Vec3::new(0.0, 1.8, 0.0)),
+      ViewBob::default(),
+      Lean::default(),
     ))
     .id();
 
+  let body_rotation = Quat::from_euler(EulerRot::XYZ, 0.06, 0.0, 0.0);
   let body = commands
     .spawn((
       Name::new("Body"),
       Body,
+      WeaponSway::new(body_rotation),
       PbrBundle {
         mesh: meshes.add(Cuboid::new(0.4, 0.4, 1.0)),
         material: materials.add(Color::Srgba(css::BEIGE)),
-        transform: Transform::from_xyz(1.0, -0.8, -0.4).with_rotation(Quat::from_euler(
-          EulerRot::XYZ,
-          0.06,
-          0.0,
-          0.0,
-        )),
+        transform: Transform::from_xyz(1.0, -0.8, -0.4).with_rotation(body_rotation),
         ..default()
       },
     ))
@@ -1,3 +1,5 @@
+use std::hash::Hash;
+
 use bevy::prelude::*;
 
 #[derive(Resource, Debug)]
@@ -11,12 +13,23 @@ pub struct PlayerInput {
   pub jump: KeyCode,
   /// 走る
   pub dash: KeyCode,
+  /// しゃがむ(走りながらだとスライディング)
+  pub crouch: KeyCode,
   /// 高速移動
   pub blink: KeyCode,
+  /// 近接攻撃
+  pub melee: KeyCode,
+  /// 左右に体を傾ける
+  pub lean_left: KeyCode,
+  pub lean_right: KeyCode,
   // アイテム切り替え
   pub item_1: KeyCode,
   pub item_2: KeyCode,
   pub item_3: KeyCode,
+  pub item_4: KeyCode,
+  pub item_5: KeyCode,
+  pub item_6: KeyCode,
+  pub item_7: KeyCode,
   // マウス
   /// 発射
   pub fire: MouseButton,
@@ -31,11 +44,52 @@ impl Default for PlayerInput {
       right: KeyCode::KeyD,
       jump: KeyCode::Space,
       dash: KeyCode::KeyV,
+      crouch: KeyCode::ControlLeft,
       blink: KeyCode::KeyQ,
+      melee: KeyCode::KeyF,
+      lean_left: KeyCode::KeyZ,
+      lean_right: KeyCode::KeyC,
       item_1: KeyCode::Digit1,
       item_2: KeyCode::Digit2,
       item_3: KeyCode::Digit3,
+      item_4: KeyCode::Digit4,
+      item_5: KeyCode::Digit5,
+      item_6: KeyCode::Digit6,
+      item_7: KeyCode::Digit7,
       fire: MouseButton::Left,
     }
   }
 }
+
+/// `FixedUpdate`で読む入力
+/// 固定タイムステップが実行されないフレームで押したボタンも取りこぼさないように
+/// 押した・離したことを次のステップまで保つ
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub struct FixedInput<T: Copy + Eq + Hash + Send + Sync + 'static>(ButtonInput<T>);
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Default for FixedInput<T> {
+  fn default() -> Self {
+    Self(ButtonInput::default())
+  }
+}
+
+// `InputSystem`の後に実行する
+pub(super) fn latch_fixed_input<T: Copy + Eq + Hash + Send + Sync + 'static>(
+  input: Res<ButtonInput<T>>,
+  mut fixed_input: ResMut<FixedInput<T>>,
+) {
+  for &button in input.get_just_pressed() {
+    fixed_input.press(button);
+  }
+
+  for &button in input.get_just_released() {
+    fixed_input.release(button);
+  }
+}
+
+// ステップごとにjust_pressedとjust_releasedを消す
+pub(super) fn clear_fixed_input<T: Copy + Eq + Hash + Send + Sync + 'static>(
+  mut fixed_input: ResMut<FixedInput<T>>,
+) {
+  fixed_input.clear();
+}
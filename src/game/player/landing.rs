@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::game::health::DamageEvent;
+
+use super::{movement::GroundSensor, Player};
+
+/// 着地したときに送られる
+/// カメラの揺れや着地音はこれを読んで再生する
+#[derive(Event, Debug)]
+pub struct Landed {
+  pub entity: Entity,
+  /// 着地した瞬間の下向きの速さ
+  pub impact_speed: f32,
+}
+
+/// 落下ダメージの曲線
+#[derive(Component, Reflect, Debug)]
+pub struct FallDamage {
+  /// この速さまではダメージを受けない
+  pub safe_speed: f32,
+  /// この速さでmax_damageを受ける
+  pub lethal_speed: f32,
+  /// 受けるダメージの上限(lethal_speedより速く落ちても増えない)
+  pub max_damage: f32,
+  /// 曲線の形(1.0で直線、大きいほど高いところからの落下で急に増える)
+  pub exponent: f32,
+}
+
+impl Default for FallDamage {
+  fn default() -> Self {
+    Self {
+      safe_speed: 20.0,
+      lethal_speed: 45.0,
+      max_damage: 100.0,
+      exponent: 2.0,
+    }
+  }
+}
+
+impl FallDamage {
+  pub fn damage(&self, impact_speed: f32) -> f32 {
+    let range = self.lethal_speed - self.safe_speed;
+    if impact_speed <= self.safe_speed || range <= 0.0 {
+      return 0.0;
+    }
+
+    ((impact_speed - self.safe_speed) / range)
+      .min(1.0)
+      .powf(self.exponent)
+      * self.max_damage
+  }
+}
+
+// `GroundSensor::grounded`が切り替わった瞬間の`Player::vertical_accel`から着地の速さを求める
+// `update_grounded`の後に実行する
+pub(super) fn update_landing(
+  mut landed_writer: EventWriter<Landed>,
+  mut damage_event_writer: EventWriter<DamageEvent>,
+  player_query: Query<(Entity, &Player, &GroundSensor, Option<&FallDamage>)>,
+) {
+  for (entity, player, ground_sensor, fall_damage) in player_query.iter() {
+    if !ground_sensor.grounded || ground_sensor.was_grounded {
+      continue;
+    }
+
+    let impact_speed = (-player.vertical_velocity()).max(0.0);
+    landed_writer.send(Landed {
+      entity,
+      impact_speed,
+    });
+
+    let damage = fall_damage.map_or(0.0, |fall_damage| fall_damage.damage(impact_speed));
+    if damage > 0.0 {
+      damage_event_writer.send(DamageEvent {
+        target: entity,
+        amount: damage,
+      });
+    }
+  }
+}
+
+// カメラの揺れや着地音ができるまではログに出して確かめる
+pub(super) fn log_landings(mut landed_reader: EventReader<Landed>) {
+  for event in landed_reader.read() {
+    debug!("{:?} landed at {}", event.entity, event.impact_speed);
+  }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::{
+  camera_controller::{CameraController, CameraRig},
+  input::PlayerInput,
+};
+
+/// 左右に体を傾けて角から覗く
+/// カメラに付け、武器はカメラの子なので一緒に傾く
+#[derive(Component, Reflect, Debug)]
+pub struct Lean {
+  /// 横にずらす距離
+  pub distance: f32,
+  /// 傾ける角度
+  pub angle: f32,
+  /// 傾ける速さ(1秒あたりの割合)
+  pub speed: f32,
+  /// 壁との間に空ける距離
+  pub margin: f32,
+  /// 壁に当たるか調べる球の半径
+  pub probe_radius: f32,
+  /// 現在の傾き(-1.0で左、1.0で右)
+  pub amount: f32,
+}
+
+impl Default for Lean {
+  fn default() -> Self {
+    Self {
+      distance: 0.5,
+      angle: 15_f32.to_radians(),
+      speed: 6.0,
+      margin: 0.1,
+      probe_radius: 0.2,
+      amount: 0.0,
+    }
+  }
+}
+
+// 傾けた先に壁があればその手前までしか傾けない
+// `update_camera_controller`の前に実行する
+pub(super) fn update_lean(
+  time: Res<Time>,
+  keyboard_input: Res<ButtonInput<KeyCode>>,
+  key: Res<PlayerInput>,
+  rapier_context: Res<RapierContext>,
+  mut camera_query: Query<(
+    &mut Lean,
+    &mut CameraController,
+    &mut CameraRig,
+    &GlobalTransform,
+    &Parent,
+  )>,
+) {
+  for (mut lean, mut camera_controller, mut camera_rig, global_transform, player) in
+    camera_query.iter_mut()
+  {
+    let mut target = 0.0;
+    if keyboard_input.pressed(key.lean_left) {
+      target -= 1.0;
+    }
+    if keyboard_input.pressed(key.lean_right) {
+      target += 1.0;
+    }
+
+    // 傾けていない頭の位置から傾ける向きに球を飛ばす
+    let right = global_transform.right();
+    let origin = global_transform.translation() - right * camera_rig.lean.x;
+    let limit = if target == 0.0 || lean.distance <= 0.0 {
+      1.0
+    } else {
+      rapier_context
+        .cast_shape(
+          origin,
+          Quat::IDENTITY,
+          *right * target,
+          &Collider::ball(lean.probe_radius),
+          ShapeCastOptions::with_max_time_of_impact(lean.distance + lean.margin),
+          QueryFilter::default()
+            .exclude_collider(player.get())
+            .exclude_sensors(),
+        )
+        .map_or(1.0, |(_, hit)| {
+          ((hit.time_of_impact - lean.margin) / lean.distance).clamp(0.0, 1.0)
+        })
+    };
+
+    let step = lean.speed * time.delta_seconds();
+    let target = target * limit;
+    lean.amount += (target - lean.amount).clamp(-step, step);
+
+    // 右に傾けるときは時計回りに回す
+    camera_controller.roll -= lean.amount * lean.angle;
+
+    camera_rig.lean = Vec3::X * lean.amount * lean.distance;
+  }
+}
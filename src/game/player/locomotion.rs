@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+
+use super::{
+  input::{FixedInput, PlayerInput},
+  movement::GroundSensor,
+  stamina::Stamina,
+  stance::Stance,
+  Player,
+};
+
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LocomotionState {
+  #[default]
+  Idle,
+  Walk,
+  Sprint,
+  Crouch,
+  Slide,
+  /// 接地していない
+  Airborne,
+}
+
+#[derive(Component, Reflect, Debug, Default)]
+pub struct Locomotion {
+  pub state: LocomotionState,
+  /// スライディングの残り時間
+  pub slide: Timer,
+}
+
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct LocomotionSpeed {
+  /// 目標の水平方向の移動速度
+  pub speed: f32,
+  /// 目標の速度に近づける加速度
+  pub acceleration: f32,
+}
+
+/// 状態ごとの速度と加速度
+#[derive(Component, Reflect, Debug)]
+pub struct LocomotionSettings {
+  pub idle: LocomotionSpeed,
+  pub walk: LocomotionSpeed,
+  pub sprint: LocomotionSpeed,
+  pub crouch: LocomotionSpeed,
+  /// speedはスライディングを始めたときの速度で、accelerationで減速する
+  pub slide: LocomotionSpeed,
+  pub airborne: LocomotionSpeed,
+  /// スライディングを続けられる時間
+  pub slide_time: f32,
+}
+
+impl Default for LocomotionSettings {
+  fn default() -> Self {
+    Self {
+      idle: LocomotionSpeed {
+        speed: 0.0,
+        acceleration: 60.0,
+      },
+      walk: LocomotionSpeed {
+        speed: 8.0,
+        acceleration: 40.0,
+      },
+      sprint: LocomotionSpeed {
+        speed: 20.0,
+        acceleration: 30.0,
+      },
+      crouch: LocomotionSpeed {
+        speed: 4.0,
+        acceleration: 40.0,
+      },
+      slide: LocomotionSpeed {
+        speed: 24.0,
+        acceleration: 16.0,
+      },
+      airborne: LocomotionSpeed {
+        speed: 8.0,
+        acceleration: 4.0,
+      },
+      slide_time: 0.8,
+    }
+  }
+}
+
+impl LocomotionSettings {
+  pub fn get(&self, state: LocomotionState) -> LocomotionSpeed {
+    match state {
+      LocomotionState::Idle => self.idle,
+      LocomotionState::Walk => self.walk,
+      LocomotionState::Sprint => self.sprint,
+      LocomotionState::Crouch => self.crouch,
+      LocomotionState::Slide => self.slide,
+      LocomotionState::Airborne => self.airborne,
+    }
+  }
+}
+
+/// 状態が変わったときに送られる
+/// HUDやアニメーション、音はこれを読んで切り替える
+#[derive(Event, Debug)]
+pub struct LocomotionChanged {
+  pub entity: Entity,
+  pub from: LocomotionState,
+  pub to: LocomotionState,
+}
+
+type LocomotionQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static mut Player,
+    &'static mut Locomotion,
+    &'static LocomotionSettings,
+    &'static GroundSensor,
+    Option<&'static Stance>,
+    Option<&'static mut Stamina>,
+  ),
+>;
+
+// 入力と接地状態から状態を決め、水平方向の移動速度を変える
+// `update_movement_input`で入力が`Player::direction`に入った後に実行する
+pub(super) fn update_locomotion(
+  time: Res<Time>,
+  keyboard_input: Res<FixedInput<KeyCode>>,
+  key: Res<PlayerInput>,
+  mut locomotion_changed_writer: EventWriter<LocomotionChanged>,
+  mut player_query: LocomotionQuery,
+) {
+  for (entity, mut player, mut locomotion, settings, ground_sensor, stance, mut stamina) in
+    player_query.iter_mut()
+  {
+    let moving = player.direction.x != 0.0 || player.direction.z != 0.0;
+    // スタミナを使い切ったら回復するまで走れない
+    let sprint =
+      keyboard_input.pressed(key.dash) && stamina.as_deref().is_none_or(Stamina::can_sprint);
+    // 天井があって立ち上がれないときはしゃがみ続ける
+    let crouch = keyboard_input.pressed(key.crouch) || stance.is_some_and(|stance| stance.blocked);
+
+    locomotion.slide.tick(time.delta());
+
+    let current = locomotion.state;
+    let next = if !ground_sensor.grounded {
+      LocomotionState::Airborne
+    } else if (current == LocomotionState::Slide && crouch && !locomotion.slide.finished())
+      || (current == LocomotionState::Sprint && moving && crouch)
+    {
+      // スライディングを続けるか、走りながらしゃがんで始める
+      LocomotionState::Slide
+    } else if crouch {
+      LocomotionState::Crouch
+    } else if !moving {
+      LocomotionState::Idle
+    } else if sprint {
+      LocomotionState::Sprint
+    } else {
+      LocomotionState::Walk
+    };
+
+    if next != current {
+      if next == LocomotionState::Slide {
+        locomotion.slide = Timer::from_seconds(settings.slide_time, TimerMode::Once);
+        player.horizontal_speed = settings.slide.speed;
+      }
+
+      locomotion.state = next;
+      locomotion_changed_writer.send(LocomotionChanged {
+        entity,
+        from: current,
+        to: next,
+      });
+    }
+
+    // スライディング中はしゃがみの速度まで減速する
+    let (target, acceleration) = match next {
+      LocomotionState::Slide => (settings.crouch.speed, settings.slide.acceleration),
+      state => {
+        let speed = settings.get(state);
+        (speed.speed, speed.acceleration)
+      }
+    };
+
+    let delta = acceleration * time.delta_seconds();
+    player.horizontal_speed += (target - player.horizontal_speed).clamp(-delta, delta);
+
+    if next == LocomotionState::Sprint {
+      if let Some(stamina) = stamina.as_deref_mut() {
+        stamina.drain(stamina.sprint_cost * time.delta_seconds());
+      }
+    }
+  }
+}
+
+// HUDやアニメーションができるまではログに出して確かめる
+pub(super) fn log_locomotion_changes(
+  mut locomotion_changed_reader: EventReader<LocomotionChanged>,
+) {
+  for event in locomotion_changed_reader.read() {
+    debug!(
+      "locomotion of {:?}: {:?} -> {:?}",
+      event.entity, event.from, event.to
+    );
+  }
+}
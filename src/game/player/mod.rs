@@ -1,8 +1,20 @@
+pub(super) mod blink;
 pub(super) mod camera_controller;
+pub(super) mod climb;
 pub(super) mod core;
 pub(super) mod input;
+pub(super) mod landing;
+pub(super) mod lean;
+pub(super) mod locomotion;
+pub(super) mod momentum;
 pub(super) mod movement;
 pub(super) mod plugin;
+pub(super) mod stamina;
+pub(super) mod stance;
+pub(super) mod swim;
+pub(super) mod trigger;
+pub(super) mod view_bob;
+pub(super) mod wall_run;
 
 pub use core::*;
 pub use plugin::*;
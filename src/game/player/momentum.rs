@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+#[derive(Component, Reflect, Debug, Default)]
+pub enum MovementProfile {
+  /// 入力から毎フレーム移動量を決める
+  #[default]
+  Classic,
+  /// 速度を保ち、摩擦と加速で変化させる
+  /// エアストレイフやバニーホップができる
+  Momentum(MomentumSettings),
+}
+
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct MomentumSettings {
+  /// 地上での摩擦
+  pub ground_friction: f32,
+  /// これより遅いときはこの速度として摩擦を計算する(すぐに止まるようにする)
+  pub stop_speed: f32,
+  /// 地上での加速度
+  pub ground_acceleration: f32,
+  /// 空中での加速度
+  pub air_acceleration: f32,
+  /// 地上で入力によって出せる最大の速度
+  pub max_ground_speed: f32,
+  /// 空中で入力によって加速できる最大の速度
+  /// 小さくすると横に入力しながら視点を回したときだけ加速する(エアストレイフ)
+  pub max_air_speed: f32,
+}
+
+impl Default for MomentumSettings {
+  fn default() -> Self {
+    Self {
+      ground_friction: 6.0,
+      stop_speed: 3.0,
+      ground_acceleration: 10.0,
+      air_acceleration: 10.0,
+      max_ground_speed: 20.0,
+      max_air_speed: 1.0,
+    }
+  }
+}
+
+impl MomentumSettings {
+  /// 1フレーム分の水平方向(upに垂直な方向)の速度を求める
+  /// wish_directionは正規化されていなくてもよい
+  pub fn step(
+    &self,
+    velocity: Vec3,
+    wish_direction: Vec3,
+    wish_speed: f32,
+    up: Vec3,
+    on_ground: bool,
+    delta: f32,
+  ) -> Vec3 {
+    let wish_direction = wish_direction
+      .reject_from_normalized(up)
+      .normalize_or_zero();
+    let velocity = velocity.reject_from_normalized(up);
+
+    if on_ground {
+      let velocity = self.friction(velocity, delta);
+      accelerate(
+        velocity,
+        wish_direction,
+        wish_speed.min(self.max_ground_speed),
+        self.ground_acceleration,
+        delta,
+      )
+    } else {
+      accelerate(
+        velocity,
+        wish_direction,
+        wish_speed.min(self.max_air_speed),
+        self.air_acceleration,
+        delta,
+      )
+    }
+  }
+
+  fn friction(&self, velocity: Vec3, delta: f32) -> Vec3 {
+    let speed = velocity.length();
+    if speed <= 0.0 {
+      return velocity;
+    }
+
+    let drop = speed.max(self.stop_speed) * self.ground_friction * delta;
+    velocity * ((speed - drop).max(0.0) / speed)
+  }
+}
+
+/// 入力方向の速度がwish_speedになるまで加速する
+/// 入力方向以外の速度は制限しないので、向きを変えながら加速し続けられる
+fn accelerate(
+  velocity: Vec3,
+  wish_direction: Vec3,
+  wish_speed: f32,
+  acceleration: f32,
+  delta: f32,
+) -> Vec3 {
+  let add_speed = wish_speed - velocity.dot(wish_direction);
+  if add_speed <= 0.0 {
+    return velocity;
+  }
+
+  velocity + wish_direction * (acceleration * wish_speed * delta).min(add_speed)
+}
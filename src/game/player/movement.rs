@@ -1,9 +1,22 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use super::{input::PlayerInput, Player, PLAYER_HEIGHT, PLAYER_OFFSET};
+use crate::game::world::Platform;
+
+use super::{
+  climb::Climber,
+  finished_timer,
+  input::{FixedInput, PlayerInput},
+  momentum::MovementProfile,
+  stamina::Stamina,
+  stance::Stance,
+  swim::Swimmer,
+  wall_run::WallRun,
+  Player, PLAYER_HEIGHT, PLAYER_OFFSET, PLAYER_RADIUS,
+};
 
 pub const GRAVITY: f32 = 9.8;
+pub(super) const JUMP_HEIGHT: f32 = -80.0;
 
 #[derive(Component, Reflect)]
 pub(super) struct GroundSensor {
@@ -11,6 +24,14 @@ pub(super) struct GroundSensor {
   pub grounded: bool,
   /// time-of-impact
   pub toi: f32,
+  /// 接地している足場
+  pub ground: Option<Entity>,
+  /// 前のフレームで接地していたか
+  pub was_grounded: bool,
+  /// 足元の中心の周りを調べる距離(半径に対する割合)
+  pub probe_spread: f32,
+  /// 足場から外れてもしばらくは接地していることにする(段差の角でちらつかないように)
+  pub grace: Timer,
 }
 
 impl Default for GroundSensor {
@@ -19,19 +40,59 @@ impl Default for GroundSensor {
       grounded: Default::default(),
       // TODO:調整する
       toi: 0.16,
+      ground: None,
+      was_grounded: false,
+      probe_spread: 0.7,
+      grace: finished_timer(0.06),
+    }
+  }
+}
+
+/// 階段や小さな段差を自動で登る
+/// 変更すると`update_autostep`で`KinematicCharacterController::autostep`に反映する
+#[derive(Component, Reflect, Debug)]
+pub struct AutoStep {
+  /// 登れる段差の高さ
+  pub max_height: f32,
+  /// 段差の上に必要な奥行き
+  pub min_width: f32,
+  /// 動く物体(dynamic)の上にも登るか
+  pub include_dynamic_bodies: bool,
+}
+
+impl Default for AutoStep {
+  fn default() -> Self {
+    Self {
+      max_height: 0.35,
+      min_width: 0.2,
+      include_dynamic_bodies: false,
     }
   }
 }
 
+type MovementInputQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    &'static mut Player,
+    &'static GroundSensor,
+    Option<&'static WallRun>,
+    Option<&'static Climber>,
+    Option<&'static Swimmer>,
+    Option<&'static mut Stamina>,
+  ),
+>;
+
 // ユーザーからの入力を反映する
 pub(super) fn update_movement_input(
-  keyboard_input: Res<ButtonInput<KeyCode>>,
+  time: Res<Time>,
+  keyboard_input: Res<FixedInput<KeyCode>>,
   key: Res<PlayerInput>,
-  mut player_query: Query<(&mut Player, &GroundSensor)>,
+  mut player_query: MovementInputQuery,
 ) {
-  const JUMP_HEIGHT: f32 = -80.0;
-
-  if let Ok((mut player, ground_sensor)) = player_query.get_single_mut() {
+  if let Ok((mut player, ground_sensor, wall_run, climber, swimmer, mut stamina)) =
+    player_query.get_single_mut()
+  {
     if keyboard_input.pressed(key.forward) {
       player.direction.x += 1.0;
     }
@@ -48,18 +109,50 @@ pub(super) fn update_movement_input(
       player.direction.z += 1.0;
     }
 
-    // TODO:プレイヤーが止まったら歩きの速度にする
-    if keyboard_input.pressed(key.dash) {
-      player.horizontal_speed = 20.0;
+    player.jump_cool_time.tick(time.delta());
+    player.coyote_time.tick(time.delta());
+    player.jump_buffer.tick(time.delta());
+
+    if ground_sensor.grounded && player.vertical_accel > 0.0 {
+      // 着地したらジャンプの回数を戻す
+      player.jump_count = 0;
+      player.coyote_time.reset();
+    } else if player.jump_count == 0 && player.coyote_time.finished() {
+      // 猶予時間内にジャンプせずに足場から落ちたら1回目のジャンプを使ったことにする
+      player.jump_count = 1;
     }
 
-    if ground_sensor.grounded && player.vertical_accel > 0.0 && keyboard_input.pressed(key.jump) {
-      // 重力とJUMP_HEIGHTで打ち消されないようにする
-      if player.vertical_accel > GRAVITY {
-        player.vertical_accel = GRAVITY;
-      }
+    if keyboard_input.just_pressed(key.jump) {
+      player.jump_buffer.reset();
+    }
+    let buffered = !player.jump_buffer.finished();
+
+    // 壁走り中やはしごを登っているときのジャンプは
+    // `update_wall_run`や`update_climb`で壁やはしごから離れるのに使い
+    // 水中では`update_swim`で上に泳ぐのに使う
+    if wall_run.is_some_and(WallRun::running)
+      || climber.is_some_and(Climber::climbing)
+      || swimmer.is_some_and(|swimmer| swimmer.submerged)
+    {
+      return;
+    }
+
+    // 地上では押し続けてもジャンプし、空中では押し直したときだけジャンプする
+    let jump_pressed = if player.jump_count == 0 {
+      keyboard_input.pressed(key.jump) || buffered
+    } else {
+      buffered
+    };
 
-      player.vertical_accel += JUMP_HEIGHT;
+    // スタミナが足りなければジャンプしない
+    if jump_pressed
+      && player.jump_count < player.jump_max_count
+      && player.jump_cool_time.finished()
+      && stamina
+        .as_deref_mut()
+        .is_none_or(|stamina| stamina.try_spend(stamina.jump_cost))
+    {
+      player.jump();
     }
   }
 }
@@ -72,15 +165,31 @@ pub(super) fn update_movement(
     &Transform,
     &mut KinematicCharacterController,
     &GroundSensor,
+    Option<&MovementProfile>,
   )>,
 ) {
-  if let Ok((mut player, player_transform, mut controller, ground_sensor)) =
+  if let Ok((mut player, player_transform, mut controller, ground_sensor, profile)) =
     player_query.get_single_mut()
   {
     player.direction = player.direction.x * player_transform.forward()
       + player.direction.z * player_transform.right();
 
-    if ground_sensor.grounded && player.vertical_accel >= 0.0 {
+    // ジャンプした瞬間は地上として扱わない(摩擦を掛けないのでバニーホップができる)
+    let on_ground = ground_sensor.grounded && player.vertical_accel >= 0.0;
+
+    player.horizontal_velocity = match profile {
+      Some(MovementProfile::Momentum(settings)) => settings.step(
+        player.horizontal_velocity,
+        player.direction,
+        player.horizontal_speed,
+        player.up,
+        on_ground,
+        time.delta_seconds(),
+      ),
+      _ => player.horizontal(player.direction * player.horizontal_speed),
+    };
+
+    if on_ground {
       // 弱い重力を加える
       player.vertical_accel = (player.vertical_accel
         - player.vertical_speed * 6.0 * time.delta_seconds())
@@ -88,13 +197,32 @@ pub(super) fn update_movement(
     } else {
       // 重力を加える
       player.vertical_accel = (player.vertical_accel
-        + GRAVITY * player.vertical_speed * time.delta_seconds())
+        + GRAVITY * player.vertical_speed * player.gravity_scale * time.delta_seconds())
       .clamp(-500.0, 500.0);
     }
 
-    player.direction.y -= player.vertical_accel * 0.2;
+    player.gravity_scale = 1.0;
+
+    let vertical_velocity = player.up * player.vertical_velocity();
+
+    // 外から受けた速度は地上では摩擦で早く止まる
+    let friction = if on_ground {
+      player.external_friction
+    } else {
+      player.external_air_friction
+    };
+    player.external_velocity *= (-friction * time.delta_seconds()).exp();
+    if player.external_velocity.length_squared() < 0.01 {
+      player.external_velocity = Vec3::ZERO;
+    }
+
+    let horizontal_velocity = player.horizontal_velocity * player.speed_scale;
+    player.speed_scale = 1.0;
 
-    let translation = (player.direction * player.horizontal_speed).with_y(player.direction.y)
+    let translation = (horizontal_velocity
+      + vertical_velocity
+      + player.inherited_velocity
+      + player.external_velocity)
       * time.delta_seconds();
 
     controller.translation = Some(translation);
@@ -102,23 +230,388 @@ pub(super) fn update_movement(
   }
 }
 
+type GroundSensorQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static mut GroundSensor,
+    &'static Transform,
+    Option<&'static Stance>,
+    Option<&'static Player>,
+  ),
+>;
+
 pub(super) fn update_grounded(
+  time: Res<Time>,
   rapier_context: Res<RapierContext>,
-  mut ground_sensor_query: Query<(&mut GroundSensor, &Transform)>,
+  mut ground_sensor_query: GroundSensorQuery,
 ) {
   // ray castでも良さそう？->ray castにした
   // todo:おかしかったらshape castに戻す
-  for (mut ground_sensor, transform) in ground_sensor_query.iter_mut() {
-    ground_sensor.grounded = rapier_context
-      .cast_ray(
-        transform
-          .translation
-          .with_y(transform.translation.y - PLAYER_HEIGHT + PLAYER_OFFSET),
-        -Vec3::Y,
-        ground_sensor.toi,
-        true,
-        QueryFilter::exclude_kinematic(),
+  for (entity, mut ground_sensor, transform, stance, player) in ground_sensor_query.iter_mut() {
+    // しゃがんでいるときは足元の位置が変わる
+    let height = stance.map_or(PLAYER_HEIGHT, Stance::height);
+    // 重力の向きが変わっているときは足元もその向きにある
+    let up = player.map_or(Vec3::Y, |player| player.up);
+
+    // 段差の角に立っていても当たるように、足元の中心と周りの4点から調べる
+    let foot = transform.translation - up * (height - PLAYER_OFFSET);
+    let spread = PLAYER_RADIUS * ground_sensor.probe_spread;
+    let forward = transform.forward() * spread;
+    let right = transform.right() * spread;
+
+    // 動く足場(kinematic)にも当たるように自分だけを除外する
+    let ground = [Vec3::ZERO, forward, -forward, right, -right]
+      .into_iter()
+      .find_map(|offset| {
+        rapier_context
+          .cast_ray(
+            foot + offset,
+            -up,
+            ground_sensor.toi,
+            true,
+            QueryFilter::default()
+              .exclude_collider(entity)
+              .exclude_sensors(),
+          )
+          .map(|(ground, _)| ground)
+      });
+
+    ground_sensor.was_grounded = ground_sensor.grounded;
+    ground_sensor.grace.tick(time.delta());
+
+    // ジャンプした瞬間は猶予を使わずにすぐ離れる
+    let jumping = player.is_some_and(|player| player.vertical_accel < 0.0);
+    if ground.is_some() {
+      ground_sensor.ground = ground;
+      ground_sensor.grounded = true;
+      ground_sensor.grace.reset();
+    } else if jumping || ground_sensor.grace.finished() {
+      ground_sensor.ground = None;
+      ground_sensor.grounded = false;
+    }
+  }
+}
+
+// `AutoStep`が変わったら`KinematicCharacterController`に反映する
+pub(super) fn update_autostep(
+  mut player_query: Query<(&AutoStep, &mut KinematicCharacterController), Changed<AutoStep>>,
+) {
+  for (auto_step, mut controller) in player_query.iter_mut() {
+    controller.autostep = Some(CharacterAutostep {
+      max_height: CharacterLength::Absolute(auto_step.max_height),
+      min_width: CharacterLength::Absolute(auto_step.min_width),
+      include_dynamic_bodies: auto_step.include_dynamic_bodies,
+    });
+  }
+}
+
+// 乗っている足場の速度を受け継ぐ
+// 足場から離れても水平方向の勢いは着地するまで保つ
+pub(super) fn update_platform_velocity(
+  time: Res<Time>,
+  mut player_query: Query<(&mut Player, &mut Transform, &GroundSensor)>,
+  platform_query: Query<(&Platform, &GlobalTransform)>,
+) {
+  for (mut player, mut transform, ground_sensor) in player_query.iter_mut() {
+    if !ground_sensor.grounded {
+      player.inherited_velocity = player.horizontal(player.inherited_velocity);
+      continue;
+    }
+
+    player.inherited_velocity = match ground_sensor
+      .ground
+      .and_then(|ground| platform_query.get(ground).ok())
+    {
+      Some((platform, platform_transform)) => {
+        // 回転する足場の上では一緒に向きを変える
+        transform.rotate_y(platform.angular_velocity.y * time.delta_seconds());
+        platform.velocity_at(platform_transform.translation(), transform.translation)
+      }
+      None => Vec3::ZERO,
+    };
+  }
+}
+
+// 上方向を重力の範囲から求めた向きに滑らかに合わせる
+// プレイヤーと一緒に子のカメラも回転し、速度も同じだけ回転させる
+pub(super) fn update_up_alignment(
+  time: Res<Time>,
+  mut player_query: Query<(
+    &mut Player,
+    &mut Transform,
+    &mut KinematicCharacterController,
+  )>,
+) {
+  for (mut player, mut transform, mut controller) in player_query.iter_mut() {
+    if player.up.abs_diff_eq(player.target_up, 1e-4) {
+      player.up = player.target_up;
+    } else {
+      let t = 1.0 - (-player.up_align_speed * time.delta_seconds()).exp();
+      let turn = Quat::IDENTITY.slerp(Quat::from_rotation_arc(player.up, player.target_up), t);
+
+      player.up = (turn * player.up).normalize();
+      transform.rotation = (turn * transform.rotation).normalize();
+      player.horizontal_velocity = turn * player.horizontal_velocity;
+      player.inherited_velocity = turn * player.inherited_velocity;
+      player.external_velocity = turn * player.external_velocity;
+    }
+
+    controller.up = player.up;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use bevy::time::{TimePlugin, TimeUpdateStrategy};
+
+  use super::*;
+  use crate::game::player::input::clear_fixed_input;
+
+  fn setup(jump_max_count: u32, jump_cool_time: f32) -> (App, Entity) {
+    let mut app = App::new();
+    app
+      .init_resource::<Time>()
+      .init_resource::<FixedInput<KeyCode>>()
+      .init_resource::<PlayerInput>()
+      .add_systems(Update, update_movement_input);
+
+    let player = app
+      .world_mut()
+      .spawn((
+        Player {
+          vertical_accel: GRAVITY,
+          jump_max_count,
+          jump_cool_time: Timer::from_seconds(jump_cool_time, TimerMode::Once),
+          ..default()
+        },
+        GroundSensor {
+          grounded: true,
+          ..default()
+        },
+      ))
+      .id();
+
+    // 最初のクールタイムを終わらせる
+    step(&mut app, jump_cool_time);
+
+    (app, player)
+  }
+
+  fn step(app: &mut App, seconds: f32) {
+    app
+      .world_mut()
+      .resource_mut::<Time>()
+      .advance_by(Duration::from_secs_f32(seconds));
+    app.update();
+  }
+
+  fn press_jump(app: &mut App, seconds: f32) {
+    let mut input = app.world_mut().resource_mut::<FixedInput<KeyCode>>();
+    input.press(KeyCode::Space);
+    step(app, seconds);
+
+    let mut input = app.world_mut().resource_mut::<FixedInput<KeyCode>>();
+    input.release(KeyCode::Space);
+    input.clear();
+  }
+
+  fn set_grounded(app: &mut App, player: Entity, grounded: bool) {
+    app
+      .world_mut()
+      .get_mut::<GroundSensor>(player)
+      .unwrap()
+      .grounded = grounded;
+  }
+
+  fn land(app: &mut App, player: Entity) {
+    set_grounded(app, player, true);
+    app
+      .world_mut()
+      .get_mut::<Player>(player)
+      .unwrap()
+      .vertical_accel = GRAVITY;
+  }
+
+  fn jump_count(app: &App, player: Entity) -> u32 {
+    app.world().get::<Player>(player).unwrap().jump_count
+  }
+
+  fn apply_translation(mut query: Query<(&mut Transform, &KinematicCharacterController)>) {
+    for (mut transform, controller) in query.iter_mut() {
+      transform.translation += controller.translation.unwrap_or_default();
+    }
+  }
+
+  // 前に進みながらジャンプし、1秒後の位置を返す
+  fn simulate(fps: u32) -> Vec3 {
+    let mut app = App::new();
+    app
+      .add_plugins(TimePlugin)
+      .insert_resource(TimeUpdateStrategy::ManualDuration(
+        Duration::from_secs(1) / fps,
+      ))
+      .init_resource::<FixedInput<KeyCode>>()
+      .init_resource::<PlayerInput>()
+      .add_systems(
+        FixedUpdate,
+        (update_movement_input, update_movement, apply_translation).chain(),
       )
-      .is_some();
+      .add_systems(FixedPostUpdate, clear_fixed_input::<KeyCode>);
+
+    let player = app
+      .world_mut()
+      .spawn((
+        Player {
+          horizontal_speed: 8.0,
+          vertical_speed: 18.0,
+          jump_max_count: 2,
+          ..default()
+        },
+        GroundSensor::default(),
+        Transform::default(),
+        KinematicCharacterController::default(),
+      ))
+      .id();
+
+    let mut input = app.world_mut().resource_mut::<FixedInput<KeyCode>>();
+    input.press(KeyCode::KeyW);
+    input.press(KeyCode::Space);
+
+    // 最初のフレームでは時間が進まない
+    for _ in 0..=fps {
+      app.update();
+    }
+
+    app.world().get::<Transform>(player).unwrap().translation
+  }
+
+  #[test]
+  fn jumps_up_to_max_count_in_air() {
+    let (mut app, player) = setup(2, 0.2);
+
+    press_jump(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 1);
+    assert!(app.world().get::<Player>(player).unwrap().vertical_accel < 0.0);
+
+    set_grounded(&mut app, player, false);
+    press_jump(&mut app, 0.3);
+    assert_eq!(jump_count(&app, player), 2);
+
+    press_jump(&mut app, 0.3);
+    assert_eq!(jump_count(&app, player), 2);
+  }
+
+  #[test]
+  fn cool_time_blocks_consecutive_jumps() {
+    let (mut app, player) = setup(3, 0.5);
+
+    press_jump(&mut app, 0.01);
+    set_grounded(&mut app, player, false);
+
+    press_jump(&mut app, 0.1);
+    assert_eq!(jump_count(&app, player), 1);
+
+    press_jump(&mut app, 0.5);
+    assert_eq!(jump_count(&app, player), 2);
+  }
+
+  #[test]
+  fn landing_resets_jump_count() {
+    let (mut app, player) = setup(2, 0.2);
+
+    press_jump(&mut app, 0.01);
+    set_grounded(&mut app, player, false);
+    press_jump(&mut app, 0.3);
+    assert_eq!(jump_count(&app, player), 2);
+
+    set_grounded(&mut app, player, true);
+    app
+      .world_mut()
+      .get_mut::<Player>(player)
+      .unwrap()
+      .vertical_accel = GRAVITY;
+    step(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 0);
+  }
+
+  #[test]
+  fn walking_off_a_ledge_uses_the_first_jump() {
+    let (mut app, player) = setup(2, 0.2);
+
+    set_grounded(&mut app, player, false);
+    step(&mut app, 0.2);
+    assert_eq!(jump_count(&app, player), 1);
+
+    press_jump(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 2);
+  }
+
+  #[test]
+  fn coyote_time_allows_jump_after_leaving_ground() {
+    let (mut app, player) = setup(1, 0.2);
+
+    set_grounded(&mut app, player, false);
+    step(&mut app, 0.05);
+    assert_eq!(jump_count(&app, player), 0);
+
+    press_jump(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 1);
+    assert!(app.world().get::<Player>(player).unwrap().vertical_accel < 0.0);
+  }
+
+  #[test]
+  fn no_jump_after_coyote_time() {
+    let (mut app, player) = setup(1, 0.2);
+
+    set_grounded(&mut app, player, false);
+    step(&mut app, 0.2);
+    press_jump(&mut app, 0.01);
+
+    assert_eq!(jump_count(&app, player), 1);
+    assert!(app.world().get::<Player>(player).unwrap().vertical_accel > 0.0);
+  }
+
+  #[test]
+  fn buffered_jump_fires_on_landing() {
+    let (mut app, player) = setup(1, 0.2);
+
+    press_jump(&mut app, 0.01);
+    set_grounded(&mut app, player, false);
+    step(&mut app, 0.3);
+
+    // 空中でジャンプを使い切った後に押す
+    press_jump(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 1);
+
+    land(&mut app, player);
+    step(&mut app, 0.05);
+    assert_eq!(jump_count(&app, player), 1);
+    assert!(app.world().get::<Player>(player).unwrap().vertical_accel < 0.0);
+  }
+
+  #[test]
+  fn movement_does_not_depend_on_frame_rate() {
+    let slow = simulate(32);
+    assert_ne!(slow, Vec3::ZERO);
+    assert_eq!(slow, simulate(320));
+  }
+
+  #[test]
+  fn expired_jump_buffer_is_ignored() {
+    let (mut app, player) = setup(1, 0.2);
+
+    press_jump(&mut app, 0.01);
+    set_grounded(&mut app, player, false);
+    step(&mut app, 0.3);
+    press_jump(&mut app, 0.01);
+    step(&mut app, 0.2);
+
+    land(&mut app, player);
+    step(&mut app, 0.01);
+    assert_eq!(jump_count(&app, player), 0);
   }
 }
@@ -1,13 +1,31 @@
-use bevy::prelude::*;
+use bevy::{input::InputSystem, prelude::*};
 
 use crate::game::state::GameState;
 
 use super::{
-  camera_controller::{update_camera_controller, CameraController},
+  blink::{log_blinks, update_blink, update_blink_input, Blink, BlinkEvent},
+  camera_controller::{update_camera_controller, update_camera_rig, CameraController, CameraRig},
+  climb::{update_climb, Climber},
   init_player,
-  input::PlayerInput,
-  movement::{update_grounded, update_movement, update_movement_input, GroundSensor},
-  update_grounded_color, Body, Player,
+  input::{clear_fixed_input, latch_fixed_input, FixedInput, PlayerInput},
+  landing::{log_landings, update_landing, FallDamage, Landed},
+  lean::{update_lean, Lean},
+  locomotion::{
+    log_locomotion_changes, update_locomotion, Locomotion, LocomotionChanged, LocomotionSettings,
+  },
+  momentum::MovementProfile,
+  movement::{
+    update_autostep, update_grounded, update_movement, update_movement_input,
+    update_platform_velocity, update_up_alignment, AutoStep, GroundSensor,
+  },
+  stamina::{update_stamina, Stamina},
+  stance::{update_stance, Stance},
+  swim::{update_swim, Swimmer},
+  trigger::{log_teleports, update_triggers, Teleported, TriggerContacts},
+  update_grounded_color,
+  view_bob::{update_view_bob, update_weapon_sway, ViewBob, WeaponSway},
+  wall_run::{update_wall_run, update_wall_run_tilt, WallRun},
+  Body, Player,
 };
 
 pub struct PlayerPlugin;
@@ -15,26 +33,100 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
   fn build(&self, app: &mut App) {
     app
+      .add_event::<BlinkEvent>()
+      .add_event::<Landed>()
+      .add_event::<LocomotionChanged>()
+      .add_event::<Teleported>()
       .add_systems(Startup, init_player)
       .add_systems(
-        Update,
+        PreUpdate,
+        (
+          latch_fixed_input::<KeyCode>,
+          latch_fixed_input::<MouseButton>,
+        )
+          .after(InputSystem),
+      )
+      // rapierは`in_fixed_schedule`で`FixedPostUpdate`に実行されるので、
+      // `FixedUpdate`で動かした分は同じステップの物理演算に反映される
+      .add_systems(
+        FixedUpdate,
         (
           (
+            log_blinks.after(update_blink_input),
+            log_landings.after(update_landing),
+            log_locomotion_changes.after(update_locomotion),
+            log_teleports.after(update_triggers),
             update_movement,
-            update_grounded_color,
             update_grounded.after(update_movement),
+            update_landing.after(update_grounded),
+            update_platform_velocity.before(update_movement),
+            update_autostep.before(update_movement),
+            update_blink.after(update_movement),
+            update_stamina.after(update_movement),
           ),
+          // `Player`の同じフィールドを書き換えるシステムがあるので、
+          // 実行順で結果が変わらないように順番を固定する
+          (
+            update_movement_input,
+            update_locomotion,
+            update_stance,
+            update_blink_input,
+            update_wall_run,
+            update_climb,
+            update_swim,
+            update_triggers,
+            update_up_alignment,
+          )
+            .chain()
+            .after(update_platform_velocity)
+            .before(update_movement)
+            .run_if(in_state(GameState::InGame)),
+        ),
+      )
+      .add_systems(
+        FixedPostUpdate,
+        (
+          clear_fixed_input::<KeyCode>,
+          clear_fixed_input::<MouseButton>,
+        ),
+      )
+      .add_systems(
+        Update,
+        (
+          update_grounded_color,
           (
             update_camera_controller,
-            update_movement_input.before(update_movement),
+            update_wall_run_tilt.before(update_camera_controller),
+            update_lean.before(update_camera_controller),
+            update_view_bob.after(update_camera_controller),
+            update_camera_rig.after(update_lean).after(update_view_bob),
+            update_weapon_sway.after(update_camera_controller),
           )
             .run_if(in_state(GameState::InGame)),
         ),
       )
+      .init_resource::<FixedInput<KeyCode>>()
+      .init_resource::<FixedInput<MouseButton>>()
       .init_resource::<PlayerInput>()
+      .register_type::<AutoStep>()
+      .register_type::<Blink>()
       .register_type::<Body>()
       .register_type::<CameraController>()
+      .register_type::<CameraRig>()
+      .register_type::<Climber>()
+      .register_type::<FallDamage>()
       .register_type::<GroundSensor>()
-      .register_type::<Player>();
+      .register_type::<Lean>()
+      .register_type::<Locomotion>()
+      .register_type::<LocomotionSettings>()
+      .register_type::<MovementProfile>()
+      .register_type::<Player>()
+      .register_type::<Stamina>()
+      .register_type::<Stance>()
+      .register_type::<Swimmer>()
+      .register_type::<TriggerContacts>()
+      .register_type::<ViewBob>()
+      .register_type::<WallRun>()
+      .register_type::<WeaponSway>();
   }
 }
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+/// 走る・ジャンプ・ブリンクで消費する
+#[derive(Component, Reflect, Debug)]
+pub struct Stamina {
+  pub current: f32,
+  pub max: f32,
+  /// 走っている間に1秒あたり消費する量
+  pub sprint_cost: f32,
+  /// ジャンプ1回で消費する量
+  pub jump_cost: f32,
+  /// ブリンク1回で消費する量
+  pub blink_cost: f32,
+  /// 1秒あたりに回復する量
+  pub regen_rate: f32,
+  /// 最後に消費してから回復を始めるまでの時間
+  pub regen_delay: Timer,
+  /// 使い切ってから回復しきるまで(maxのrecover_fraction倍)は走れない
+  pub exhausted: bool,
+  pub recover_fraction: f32,
+}
+
+impl Default for Stamina {
+  fn default() -> Self {
+    Self {
+      current: 100.0,
+      max: 100.0,
+      sprint_cost: 20.0,
+      jump_cost: 10.0,
+      blink_cost: 25.0,
+      regen_rate: 30.0,
+      regen_delay: Timer::from_seconds(1.0, TimerMode::Once),
+      exhausted: false,
+      recover_fraction: 0.3,
+    }
+  }
+}
+
+impl Stamina {
+  /// 残りの割合(HUD用)
+  pub fn fraction(&self) -> f32 {
+    if self.max <= 0.0 {
+      0.0
+    } else {
+      self.current / self.max
+    }
+  }
+
+  /// 足りていれば消費してtrueを返す
+  pub fn try_spend(&mut self, cost: f32) -> bool {
+    if self.exhausted || self.current < cost {
+      return false;
+    }
+
+    self.drain(cost);
+    true
+  }
+
+  /// 足りなくても消費する(走っている間など)
+  pub fn drain(&mut self, amount: f32) {
+    if amount <= 0.0 {
+      return;
+    }
+
+    self.current = (self.current - amount).max(0.0);
+    self.regen_delay.reset();
+    if self.current <= 0.0 {
+      self.exhausted = true;
+    }
+  }
+
+  /// 走れるか
+  pub fn can_sprint(&self) -> bool {
+    !self.exhausted && self.current > 0.0
+  }
+}
+
+// 最後に消費してからregen_delayが経ったら回復する
+pub(super) fn update_stamina(time: Res<Time>, mut stamina_query: Query<&mut Stamina>) {
+  for mut stamina in stamina_query.iter_mut() {
+    stamina.regen_delay.tick(time.delta());
+    if !stamina.regen_delay.finished() {
+      continue;
+    }
+
+    stamina.current =
+      (stamina.current + stamina.regen_rate * time.delta_seconds()).min(stamina.max);
+    if stamina.exhausted && stamina.fraction() >= stamina.recover_fraction {
+      stamina.exhausted = false;
+    }
+  }
+}
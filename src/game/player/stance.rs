@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::{
+  camera_controller::CameraRig,
+  locomotion::{Locomotion, LocomotionState},
+  movement::GroundSensor,
+  Player, PLAYER_HALF_HEIGHT, PLAYER_OFFSET, PLAYER_RADIUS,
+};
+
+/// しゃがみに合わせてコライダーとカメラの高さを変える
+#[derive(Component, Reflect, Debug)]
+pub struct Stance {
+  /// 現在のカプセルの半分の高さ(半球の部分を除く)
+  pub half_height: f32,
+  /// 立っているときのhalf_height
+  pub standing_half_height: f32,
+  /// しゃがんでいるときのhalf_height
+  pub crouching_half_height: f32,
+  /// 立っているときのカメラの高さ
+  pub standing_camera_height: f32,
+  /// しゃがんでいるときのカメラの高さ
+  pub crouching_camera_height: f32,
+  /// half_heightを変える速さ(m/sec)
+  pub transition_speed: f32,
+  /// 天井があって立ち上がれない
+  pub blocked: bool,
+}
+
+impl Default for Stance {
+  fn default() -> Self {
+    Self {
+      half_height: PLAYER_HALF_HEIGHT,
+      standing_half_height: PLAYER_HALF_HEIGHT,
+      crouching_half_height: 0.5,
+      standing_camera_height: 1.8,
+      crouching_camera_height: 1.0,
+      transition_speed: 4.0,
+      blocked: false,
+    }
+  }
+}
+
+impl Stance {
+  /// 中心から足元までの高さ(`PLAYER_HEIGHT`の代わり)
+  pub fn height(&self) -> f32 {
+    self.half_height + PLAYER_RADIUS
+  }
+
+  /// しゃがみ具合(0.0で立っていて、1.0でしゃがんでいる)
+  pub fn crouch_fraction(&self) -> f32 {
+    let range = self.standing_half_height - self.crouching_half_height;
+    if range <= 0.0 {
+      0.0
+    } else {
+      ((self.standing_half_height - self.half_height) / range).clamp(0.0, 1.0)
+    }
+  }
+}
+
+type StanceQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static mut Stance,
+    &'static Locomotion,
+    &'static GroundSensor,
+    &'static mut Transform,
+    &'static mut Collider,
+  ),
+  With<Player>,
+>;
+
+// `Locomotion`の状態に合わせてコライダーの高さを変える
+// 天井がある場合は立ち上がらない
+pub(super) fn update_stance(
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  mut player_query: StanceQuery,
+  mut camera_query: Query<&mut CameraRig>,
+) {
+  for (entity, mut stance, locomotion, ground_sensor, mut transform, mut collider) in
+    player_query.iter_mut()
+  {
+    let crouching = matches!(
+      locomotion.state,
+      LocomotionState::Crouch | LocomotionState::Slide
+    );
+    let target = if crouching {
+      stance.crouching_half_height
+    } else {
+      stance.standing_half_height
+    };
+
+    // 立ち上がるときに頭がぶつからないか確認する
+    let rise = (stance.standing_half_height - stance.half_height) * 2.0;
+    stance.blocked = rise > 0.0
+      && rapier_context
+        .cast_shape(
+          transform.translation,
+          transform.rotation,
+          *transform.up(),
+          &collider,
+          ShapeCastOptions::with_max_time_of_impact(rise + PLAYER_OFFSET),
+          QueryFilter::default()
+            .exclude_collider(entity)
+            .exclude_sensors(),
+        )
+        .is_some();
+
+    let target = if stance.blocked {
+      target.min(stance.half_height)
+    } else {
+      target
+    };
+
+    let delta = stance.transition_speed * time.delta_seconds();
+    let next = stance.half_height + (target - stance.half_height).clamp(-delta, delta);
+    if next == stance.half_height {
+      continue;
+    }
+
+    // 接地しているときは足元の位置が変わらないように中心を動かす
+    if ground_sensor.grounded {
+      let up = transform.up();
+      transform.translation += up * (next - stance.half_height);
+    }
+
+    stance.half_height = next;
+    *collider = Collider::capsule_y(next, PLAYER_RADIUS);
+
+    // カメラの位置は`update_camera_rig`で立っているときの高さからのずれとして組み立てる
+    if let Ok(mut camera_rig) = camera_query.get_single_mut() {
+      camera_rig.stance =
+        (stance.crouching_camera_height - stance.standing_camera_height) * stance.crouch_fraction();
+    }
+  }
+}
@@ -0,0 +1,129 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  health::DamageEvent,
+  world::{find_water, Water},
+};
+
+use super::{
+  camera_controller::CameraController,
+  input::{FixedInput, PlayerInput},
+  movement::GRAVITY,
+  Player,
+};
+
+#[derive(Component, Reflect, Debug)]
+pub struct Swimmer {
+  /// 入っている水
+  pub water: Option<Entity>,
+  /// 頭まで沈んでいる
+  pub submerged: bool,
+  /// 上下に泳ぐ速さ
+  pub swim_speed: f32,
+  /// 水中で掛かる重力の倍率
+  pub gravity_scale: f32,
+  /// 沈んでいるときに掛かる浮力(重力の何倍か)
+  pub buoyancy: f32,
+  /// 上下の速度を目標に近づける割合(1/sec)
+  pub drag: f32,
+  /// 息が続く時間(水面に出ると戻る)
+  pub oxygen: Timer,
+  /// 息が切れた後に受けるダメージの間隔
+  pub drown_interval: Timer,
+  /// 息が切れた後に受けるダメージ
+  pub drown_damage: f32,
+}
+
+impl Default for Swimmer {
+  fn default() -> Self {
+    Self {
+      water: None,
+      submerged: false,
+      swim_speed: 4.0,
+      gravity_scale: 0.3,
+      buoyancy: 0.4,
+      drag: 4.0,
+      oxygen: Timer::from_seconds(15.0, TimerMode::Once),
+      drown_interval: Timer::from_seconds(1.0, TimerMode::Repeating),
+      drown_damage: 10.0,
+    }
+  }
+}
+
+/// 上下に泳ぐのに使う入力
+#[derive(SystemParam)]
+pub(super) struct SwimInput<'w> {
+  keyboard_input: Res<'w, FixedInput<KeyCode>>,
+  key: Res<'w, PlayerInput>,
+}
+
+// 水の中では浮力を加え、カメラの向きに上下にも泳ぐ
+// `update_movement_input`の後、`update_movement`の前に実行する
+pub(super) fn update_swim(
+  time: Res<Time>,
+  input: SwimInput,
+  rapier_context: Res<RapierContext>,
+  mut damage_event_writer: EventWriter<DamageEvent>,
+  water_query: Query<(&Water, &GlobalTransform)>,
+  mut player_query: Query<(Entity, &mut Player, &mut Swimmer, &Transform)>,
+  camera_query: Query<&GlobalTransform, (With<CameraController>, Without<Player>)>,
+) {
+  let Ok(camera_transform) = camera_query.get_single() else {
+    return;
+  };
+
+  for (entity, mut player, mut swimmer, transform) in player_query.iter_mut() {
+    let water = find_water(&rapier_context, &water_query, transform.translation);
+    swimmer.water = water.map(|(water, _, _)| water);
+    swimmer.submerged =
+      water.is_some_and(|(_, _, surface)| camera_transform.translation().y < surface);
+
+    // 息
+    if swimmer.submerged {
+      swimmer.oxygen.tick(time.delta());
+      if swimmer.oxygen.finished() {
+        swimmer.drown_interval.tick(time.delta());
+        if swimmer.drown_interval.just_finished() {
+          damage_event_writer.send(DamageEvent {
+            target: entity,
+            amount: swimmer.drown_damage,
+          });
+        }
+      }
+    } else {
+      swimmer.oxygen.reset();
+      swimmer.drown_interval.reset();
+    }
+
+    let Some((_, _, surface)) = water else {
+      continue;
+    };
+
+    // 前への入力はカメラの向きに合わせて上下にも分ける
+    let look = camera_transform.forward();
+    let mut vertical = player.direction.x * look.y;
+    player.direction.x *= look.with_y(0.0).length();
+
+    if swimmer.submerged && input.keyboard_input.pressed(input.key.jump) {
+      vertical += 1.0;
+    }
+    if input.keyboard_input.pressed(input.key.crouch) {
+      vertical -= 1.0;
+    }
+
+    // 沈んでいるほど浮力が強くなる(中心が水面にあるときは半分)
+    let depth = (surface - transform.translation.y + 0.5).clamp(0.0, 1.0);
+    let delta = time.delta_seconds();
+    player.gravity_scale *= swimmer.gravity_scale;
+    player.vertical_accel -= GRAVITY * player.vertical_speed * swimmer.buoyancy * depth * delta;
+
+    // 水の抵抗で泳ぐ速度に近づける
+    let target = vertical.clamp(-1.0, 1.0) * swimmer.swim_speed;
+    let velocity = player.vertical_velocity();
+    player.set_vertical_velocity(velocity + (target - velocity) * (swimmer.drag * delta).min(1.0));
+
+    // 水面からはジャンプで上がれる
+    player.jump_count = 0;
+  }
+}
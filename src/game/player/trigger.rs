@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  interpolation::TranslationInterpolation,
+  world::{BoostZone, GravityZone, JumpPad, Teleporter},
+};
+
+use super::Player;
+
+/// 入っている作動範囲(入った瞬間だけ作動させるために使う)
+#[derive(Component, Reflect, Debug, Default)]
+pub struct TriggerContacts {
+  pub inside: Vec<Entity>,
+}
+
+/// テレポーターで移動したときに送られる
+/// エフェクトや音はこれを読んで再生する
+#[derive(Event, Debug)]
+pub struct Teleported {
+  pub entity: Entity,
+  pub from: Vec3,
+  pub to: Vec3,
+}
+
+fn find_triggers(
+  rapier_context: &RapierContext,
+  entity: Entity,
+  transform: &Transform,
+  collider: &Collider,
+) -> Vec<Entity> {
+  let mut inside = Vec::new();
+  rapier_context.intersections_with_shape(
+    transform.translation,
+    transform.rotation,
+    collider,
+    QueryFilter::default()
+      .exclude_collider(entity)
+      .exclude_solids(),
+    |sensor| {
+      inside.push(sensor);
+      true
+    },
+  );
+  inside
+}
+
+fn yaw(transform: &GlobalTransform) -> f32 {
+  transform
+    .compute_transform()
+    .rotation
+    .to_euler(EulerRot::YXZ)
+    .0
+}
+
+type TriggerPlayerQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    Entity,
+    &'static mut Player,
+    &'static mut TriggerContacts,
+    &'static mut Transform,
+    &'static Collider,
+    Option<&'static mut TranslationInterpolation>,
+  ),
+>;
+
+type TriggerQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    &'static GlobalTransform,
+    Option<&'static JumpPad>,
+    Option<&'static BoostZone>,
+    Option<&'static GravityZone>,
+    Option<&'static Teleporter>,
+  ),
+>;
+
+// ジャンプ台、加速する範囲、重力の範囲、テレポーターを作動させる
+// `update_movement_input`の後、`update_movement`の前に実行する
+pub(super) fn update_triggers(
+  rapier_context: Res<RapierContext>,
+  mut teleported_writer: EventWriter<Teleported>,
+  mut player_query: TriggerPlayerQuery,
+  // テレポーターの行き先を探すのにも使う
+  trigger_query: TriggerQuery,
+) {
+  for (entity, mut player, mut contacts, mut transform, collider, mut interpolation) in
+    player_query.iter_mut()
+  {
+    let inside = find_triggers(&rapier_context, entity, &transform, collider);
+    let mut teleported = false;
+
+    // 重力の範囲の外では元の重力に戻す
+    player.target_up = Vec3::Y;
+
+    for &trigger in inside.iter() {
+      let Ok((trigger_transform, jump_pad, boost_zone, gravity_zone, teleporter)) =
+        trigger_query.get(trigger)
+      else {
+        continue;
+      };
+
+      // 加速と重力は中にいる間ずっと掛かる
+      if let Some(boost_zone) = boost_zone {
+        player.speed_scale *= boost_zone.multiplier;
+      }
+      if let Some(gravity_zone) = gravity_zone {
+        if let Some(down) =
+          (trigger_transform.compute_transform().rotation * gravity_zone.direction).try_normalize()
+        {
+          player.target_up = -down;
+        }
+        player.gravity_scale *= gravity_zone.strength;
+      }
+
+      if contacts.inside.contains(&trigger) {
+        continue;
+      }
+
+      if let Some(jump_pad) = jump_pad {
+        player.launch(trigger_transform.compute_transform().rotation * jump_pad.velocity);
+      }
+
+      let Some((teleporter_transform, destination)) = teleporter.and_then(|teleporter| {
+        let (destination, ..) = trigger_query.get(teleporter.destination).ok()?;
+        Some((trigger_transform, destination))
+      }) else {
+        continue;
+      };
+
+      // 入口と出口の向きの差だけ位置、向き、速度を回転させる
+      let yaw = yaw(destination) - yaw(teleporter_transform);
+      let turn = Quat::from_rotation_y(yaw);
+
+      let from = transform.translation;
+      transform.translation = destination.translation()
+        + turn * (transform.translation - teleporter_transform.translation());
+      transform.rotation = turn * transform.rotation;
+
+      player.horizontal_velocity = turn * player.horizontal_velocity;
+      player.inherited_velocity = turn * player.inherited_velocity;
+      player.external_velocity = turn * player.external_velocity;
+
+      // 移動した分は補間しない
+      if let Some(interpolation) = interpolation.as_deref_mut() {
+        *interpolation = TranslationInterpolation::new(transform.translation);
+      }
+
+      teleported_writer.send(Teleported {
+        entity,
+        from,
+        to: transform.translation,
+      });
+      teleported = true;
+      break;
+    }
+
+    // 出口の範囲に入ったことにして、すぐに戻されないようにする
+    contacts.inside = if teleported {
+      find_triggers(&rapier_context, entity, &transform, collider)
+    } else {
+      inside
+    };
+  }
+}
+
+// エフェクトや音ができるまではログに出して確かめる
+pub(super) fn log_teleports(mut teleported_reader: EventReader<Teleported>) {
+  for event in teleported_reader.read() {
+    debug!(
+      "{:?} teleported from {} to {}",
+      event.entity, event.from, event.to
+    );
+  }
+}
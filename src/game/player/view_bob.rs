@@ -0,0 +1,196 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use super::{
+  camera_controller::{CameraController, CameraRig},
+  landing::Landed,
+  movement::GroundSensor,
+  Player,
+};
+
+/// 歩いているときの頭の揺れ、立ち止まっているときの呼吸、着地したときの沈み込み
+/// カメラに付け、ずれは`CameraRig::bob`に書く
+#[derive(Component, Reflect, Debug)]
+pub struct ViewBob {
+  /// falseにすると揺れを止める(酔いやすい人向け)
+  pub enabled: bool,
+  /// 歩いているときの揺れの大きさ(左右, 上下)
+  pub bob_amplitude: Vec2,
+  /// 1mあたりの歩数
+  pub step_frequency: f32,
+  /// この速さで揺れが最大になる
+  pub bob_full_speed: f32,
+  /// 揺れの大きさを速さに合わせる速さ(1/sec)
+  pub bob_smoothing: f32,
+  /// 呼吸の上下の揺れの大きさ
+  pub breathing_amplitude: f32,
+  /// 1秒あたりの呼吸の回数
+  pub breathing_frequency: f32,
+  /// 着地の速さに対する沈み込みの初速の割合
+  pub landing_dip_scale: f32,
+  /// 沈み込みの初速の上限
+  pub landing_dip_max: f32,
+  /// 沈み込みから戻るばねの強さ
+  pub landing_stiffness: f32,
+  // 状態
+  phase: f32,
+  breathing_phase: f32,
+  weight: f32,
+  dip: f32,
+  dip_velocity: f32,
+}
+
+impl Default for ViewBob {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      bob_amplitude: Vec2::new(0.03, 0.05),
+      step_frequency: 0.3,
+      bob_full_speed: 8.0,
+      bob_smoothing: 8.0,
+      breathing_amplitude: 0.008,
+      breathing_frequency: 0.25,
+      landing_dip_scale: 0.08,
+      landing_dip_max: 2.0,
+      landing_stiffness: 120.0,
+      phase: 0.0,
+      breathing_phase: 0.0,
+      weight: 0.0,
+      dip: 0.0,
+      dip_velocity: 0.0,
+    }
+  }
+}
+
+/// 視点を回したときに武器や腕を遅れて付いてこさせる
+/// カメラの子の`Body`とインベントリに付ける
+#[derive(Component, Reflect, Debug)]
+pub struct WeaponSway {
+  /// falseにすると揺れを止める(酔いやすい人向け)
+  pub enabled: bool,
+  /// 回転した量に対して遅れる角度の割合
+  pub amount: f32,
+  /// 遅れる角度の上限
+  pub max_angle: f32,
+  /// 元の向きに戻る速さ(1/sec)
+  pub smoothing: f32,
+  /// 揺れていないときの向き(毎フレームこれに揺れを掛けて`Transform`に書く)
+  pub base_rotation: Quat,
+  // 状態
+  sway: Vec2,
+}
+
+impl Default for WeaponSway {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      amount: 0.6,
+      max_angle: 6_f32.to_radians(),
+      smoothing: 10.0,
+      base_rotation: Quat::IDENTITY,
+      sway: Vec2::ZERO,
+    }
+  }
+}
+
+impl WeaponSway {
+  pub fn new(base_rotation: Quat) -> Self {
+    Self {
+      base_rotation,
+      ..default()
+    }
+  }
+}
+
+// 移動の速さと歩幅から頭の揺れを求めてカメラの位置に加える
+// `update_camera_controller`の後に実行する
+pub(super) fn update_view_bob(
+  time: Res<Time>,
+  mut landed_reader: EventReader<Landed>,
+  player_query: Query<(Entity, &Player, &GroundSensor)>,
+  mut camera_query: Query<(&mut ViewBob, &mut CameraRig)>,
+) {
+  let (Ok((entity, player, ground_sensor)), Ok((mut view_bob, mut camera_rig))) =
+    (player_query.get_single(), camera_query.get_single_mut())
+  else {
+    return;
+  };
+  let delta = time.delta_seconds();
+
+  // カメラを持っているプレイヤーの着地だけで沈み込む
+  for landed in landed_reader
+    .read()
+    .filter(|landed| landed.entity == entity)
+  {
+    view_bob.dip_velocity -=
+      (landed.impact_speed * view_bob.landing_dip_scale).min(view_bob.landing_dip_max);
+  }
+
+  camera_rig.bob = if view_bob.enabled {
+    // 接地しているときだけ歩く速さに合わせて揺らす
+    let speed = player.horizontal(player.horizontal_velocity).length();
+    let target = if ground_sensor.grounded && view_bob.bob_full_speed > 0.0 {
+      (speed / view_bob.bob_full_speed).min(1.0)
+    } else {
+      0.0
+    };
+    view_bob.weight += (target - view_bob.weight) * (1.0 - (-view_bob.bob_smoothing * delta).exp());
+
+    // 1歩で上下に1回、左右に半分揺れる
+    view_bob.phase = (view_bob.phase + speed * view_bob.step_frequency * 0.5 * TAU * delta) % TAU;
+    view_bob.breathing_phase =
+      (view_bob.breathing_phase + view_bob.breathing_frequency * TAU * delta) % TAU;
+
+    let bob = Vec3::new(
+      view_bob.phase.sin() * view_bob.bob_amplitude.x,
+      ((view_bob.phase * 2.0).cos() - 1.0) * 0.5 * view_bob.bob_amplitude.y,
+      0.0,
+    ) * view_bob.weight;
+    let breathing = Vec3::Y
+      * view_bob.breathing_phase.sin()
+      * view_bob.breathing_amplitude
+      * (1.0 - view_bob.weight);
+
+    // 沈み込みは臨界減衰のばねで戻す
+    let stiffness = view_bob.landing_stiffness;
+    let accel = -stiffness * view_bob.dip - 2.0 * stiffness.sqrt() * view_bob.dip_velocity;
+    view_bob.dip_velocity += accel * delta;
+    view_bob.dip += view_bob.dip_velocity * delta;
+
+    bob + breathing + Vec3::Y * view_bob.dip
+  } else {
+    view_bob.dip = 0.0;
+    view_bob.dip_velocity = 0.0;
+    Vec3::ZERO
+  };
+}
+
+// カメラが回転した量から武器を遅れさせる
+// `update_camera_controller`の後に実行する
+pub(super) fn update_weapon_sway(
+  time: Res<Time>,
+  camera_query: Query<&CameraController>,
+  mut sway_query: Query<(&mut WeaponSway, &mut Transform)>,
+) {
+  let Ok(camera_controller) = camera_query.get_single() else {
+    return;
+  };
+  let delta = time.delta_seconds();
+
+  for (mut weapon_sway, mut transform) in sway_query.iter_mut() {
+    let sway = if weapon_sway.enabled {
+      let max_angle = weapon_sway.max_angle;
+      let target = (weapon_sway.sway - camera_controller.look_delta * weapon_sway.amount)
+        .clamp(Vec2::splat(-max_angle), Vec2::splat(max_angle));
+      // 回転した分だけずれてから元の向きに戻る
+      target * (-weapon_sway.smoothing * delta).exp()
+    } else {
+      Vec2::ZERO
+    };
+    weapon_sway.sway = sway;
+
+    let rotation = Quat::from_euler(EulerRot::YXZ, sway.x, sway.y, 0.0);
+    transform.rotation = rotation * weapon_sway.base_rotation;
+  }
+}
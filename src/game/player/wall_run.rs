@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::{camera_controller::CameraController, movement::GroundSensor, Player, PLAYER_RADIUS};
+
+#[derive(Component, Reflect, Debug)]
+pub struct WallRun {
+  /// コライダーの表面から壁を探す距離
+  pub probe_distance: f32,
+  /// 壁走りを始めるのに必要な水平方向の速さ
+  pub min_speed: f32,
+  /// 壁走りを続けられる時間(着地すると戻る)
+  pub duration: Timer,
+  /// 壁走り中の重力の倍率
+  pub gravity_scale: f32,
+  /// 壁ジャンプで壁から離れる速さ
+  pub jump_push: f32,
+  /// 壁走り中にカメラを傾ける角度(rad)
+  pub camera_tilt: f32,
+  /// カメラを傾ける速さ(rad/sec)
+  pub tilt_speed: f32,
+  /// 走っている壁の法線(壁走りしていなければNone)
+  pub wall_normal: Option<Vec3>,
+  /// 現在のカメラの傾き
+  pub tilt: f32,
+}
+
+impl Default for WallRun {
+  fn default() -> Self {
+    Self {
+      probe_distance: 0.3,
+      min_speed: 6.0,
+      duration: Timer::from_seconds(1.5, TimerMode::Once),
+      gravity_scale: 0.2,
+      jump_push: 12.0,
+      camera_tilt: 12_f32.to_radians(),
+      tilt_speed: 1.5,
+      wall_normal: None,
+      tilt: 0.0,
+    }
+  }
+}
+
+impl WallRun {
+  pub fn running(&self) -> bool {
+    self.wall_normal.is_some()
+  }
+}
+
+// 空中で壁に沿って走っているときに重力を弱め、ジャンプで壁から離れる
+// `update_movement_input`の後、`update_movement`の前に実行する
+pub(super) fn update_wall_run(
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  mut player_query: Query<(Entity, &mut Player, &mut WallRun, &Transform, &GroundSensor)>,
+) {
+  for (entity, mut player, mut wall_run, transform, ground_sensor) in player_query.iter_mut() {
+    let velocity = player.horizontal(player.horizontal_velocity + player.inherited_velocity);
+
+    let wall_normal = if ground_sensor.grounded {
+      wall_run.duration.reset();
+      None
+    } else if velocity.length() < wall_run.min_speed || wall_run.duration.finished() {
+      None
+    } else {
+      // 左右に壁があり、壁に沿って進んでいるか確認する
+      [transform.right(), transform.left()]
+        .into_iter()
+        .filter_map(|side| {
+          rapier_context
+            .cast_ray_and_get_normal(
+              transform.translation,
+              *side,
+              PLAYER_RADIUS + wall_run.probe_distance,
+              true,
+              QueryFilter::default()
+                .exclude_collider(entity)
+                .exclude_sensors(),
+            )
+            .map(|(_, hit)| hit.normal)
+        })
+        .find(|normal| {
+          normal.dot(player.up).abs() < 0.3 && normal.dot(velocity.normalize()).abs() < 0.5
+        })
+    };
+
+    match wall_normal {
+      Some(normal) => {
+        // 壁に付いたときは落下を止める
+        if !wall_run.running() {
+          player.vertical_accel = player.vertical_accel.min(0.0);
+        }
+
+        wall_run.duration.tick(time.delta());
+        player.gravity_scale *= wall_run.gravity_scale;
+
+        if !player.jump_buffer.finished() {
+          // 壁の法線の向きに跳ぶ(地上からのジャンプとして数える)
+          player.jump_count = 0;
+          player.jump();
+          let push = player.horizontal(normal) * wall_run.jump_push;
+          player.inherited_velocity += push;
+
+          wall_run.wall_normal = None;
+        } else {
+          wall_run.wall_normal = Some(normal);
+        }
+      }
+      None => wall_run.wall_normal = None,
+    }
+  }
+}
+
+// 壁のある側にカメラを傾ける
+// 見た目だけなので`FixedUpdate`ではなく毎フレーム`update_camera_controller`の前に実行する
+pub(super) fn update_wall_run_tilt(
+  time: Res<Time>,
+  mut player_query: Query<(&mut WallRun, &Transform)>,
+  mut camera_query: Query<&mut CameraController>,
+) {
+  for (mut wall_run, transform) in player_query.iter_mut() {
+    let target = wall_run.wall_normal.map_or(0.0, |normal| {
+      normal.dot(*transform.right()) * wall_run.camera_tilt
+    });
+    let delta = wall_run.tilt_speed * time.delta_seconds();
+    wall_run.tilt += (target - wall_run.tilt).clamp(-delta, delta);
+
+    if let Ok(mut camera_controller) = camera_query.get_single_mut() {
+      camera_controller.roll += wall_run.tilt;
+    }
+  }
+}
@@ -5,8 +5,9 @@ use bevy_rapier3d::{
 };
 
 use super::{
-  inventory::plugin::InventoryPlugin, player::PlayerPlugin, shader::ShaderPlugin,
-  shooting::ShootingPlugin, state::StatePlugin, test::TestPlugin, ui::UiPlugin, world::WorldPlugin,
+  health::HealthPlugin, interpolation::InterpolationPlugin, inventory::plugin::InventoryPlugin,
+  player::PlayerPlugin, shader::ShaderPlugin, shooting::ShootingPlugin, state::StatePlugin,
+  test::TestPlugin, ui::UiPlugin, world::WorldPlugin,
 };
 
 pub struct GamePlugin;
@@ -14,9 +15,12 @@ pub struct GamePlugin;
 impl Plugin for GamePlugin {
   fn build(&self, app: &mut bevy::prelude::App) {
     let app = app.add_plugins((
-      RapierPhysicsPlugin::<NoUserData>::default(),
+      // 移動や射撃と同じ固定タイムステップで物理演算する
+      RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule(),
       // inventory crateのPlugin
       inventory::InventoryPlugin,
+      HealthPlugin,
+      InterpolationPlugin,
       InventoryPlugin,
       PlayerPlugin,
       ShaderPlugin,
@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  health::{DamageEvent, Health},
+  player::Player,
+};
+
+use super::ProjectileBullet;
+
+/// 爆発地点を壁から少し手前に戻す距離
+const EXPLOSION_SURFACE_OFFSET: f32 = 0.05;
+
+#[derive(Component, Reflect, Debug)]
+pub struct Explosive {
+  /// 爆発の半径
+  pub radius: f32,
+  /// 爆心でのダメージ(半径の端で0になる)
+  pub damage: f32,
+  /// 爆心での外向きのインパルス
+  pub impulse: f32,
+  /// 爆発するまでの時間(Noneなら時間では爆発しない)
+  pub fuse: Option<Timer>,
+  /// 着弾時に爆発するか(`ProjectileBullet`のみ)
+  pub on_impact: bool,
+}
+
+/// 爆発が起きたときに送られる
+/// エフェクトや音はこれを読んで再生する
+#[derive(Event, Debug, Clone)]
+pub struct ExplosionEvent {
+  /// 爆発したエンティティ(遮蔽物の判定から除外する)
+  pub source: Option<Entity>,
+  pub position: Vec3,
+  pub radius: f32,
+  pub damage: f32,
+  pub impulse: f32,
+}
+
+// 着弾とfuseを確認して爆発させる
+// `update_projectile`が弾を動かす前に実行する
+pub(super) fn update_explosive(
+  mut commands: Commands,
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  mut explosion_event_writer: EventWriter<ExplosionEvent>,
+  mut explosive_query: Query<(
+    Entity,
+    &mut Explosive,
+    &Transform,
+    Option<&ProjectileBullet>,
+  )>,
+) {
+  // `GlobalTransform`は描画用に補間された位置なので、`update_projectile`と同じく`Transform`を使う
+  // (弾は原点にある`ProjectileBulletGroup`の子、グレネードは親を持たない)
+  for (entity, mut explosive, transform, projectile) in explosive_query.iter_mut() {
+    let mut position = None;
+
+    if let Some(fuse) = explosive.fuse.as_mut() {
+      if fuse.tick(time.delta()).finished() {
+        position = Some(transform.translation);
+      }
+    }
+
+    if let (true, Some(projectile)) = (explosive.on_impact, projectile) {
+      // 次のフレームまでに進む距離だけRaycastする
+      let origin = transform.translation;
+      let direction = transform.forward();
+      if let Some((_, toi)) = rapier_context.cast_ray(
+        origin,
+        *direction,
+        projectile.speed * time.delta_seconds(),
+        true,
+        QueryFilter::exclude_kinematic().exclude_sensors(),
+      ) {
+        position = Some(origin + *direction * (toi - EXPLOSION_SURFACE_OFFSET).max(0.0));
+      }
+    }
+
+    if let Some(position) = position {
+      explosion_event_writer.send(ExplosionEvent {
+        source: Some(entity),
+        position,
+        radius: explosive.radius,
+        damage: explosive.damage,
+        impulse: explosive.impulse,
+      });
+      commands.entity(entity).despawn_recursive();
+    }
+  }
+}
+
+type ExplosionTargetQuery<'w, 's> = Query<
+  'w,
+  's,
+  (
+    &'static GlobalTransform,
+    Option<&'static RigidBody>,
+    Has<Health>,
+    Option<&'static mut Player>,
+  ),
+>;
+
+// 爆発の範囲内にあるエンティティにダメージとインパルスを与える
+pub(super) fn apply_explosion(
+  mut commands: Commands,
+  rapier_context: Res<RapierContext>,
+  mut explosion_event_reader: EventReader<ExplosionEvent>,
+  mut damage_event_writer: EventWriter<DamageEvent>,
+  mut target_query: ExplosionTargetQuery,
+) {
+  for event in explosion_event_reader.read() {
+    let mut filter = QueryFilter::default().exclude_sensors();
+    if let Some(source) = event.source {
+      filter = filter.exclude_collider(source);
+    }
+
+    let mut targets = Vec::new();
+    rapier_context.intersections_with_shape(
+      event.position,
+      Quat::IDENTITY,
+      &Collider::ball(event.radius),
+      filter,
+      |entity| {
+        targets.push(entity);
+        true
+      },
+    );
+
+    for entity in targets {
+      let Ok((global_transform, rigid_body, has_health, player)) = target_query.get_mut(entity)
+      else {
+        continue;
+      };
+
+      let offset = global_transform.translation() - event.position;
+      let distance = offset.length();
+      let direction = offset.try_normalize().unwrap_or(Vec3::Y);
+
+      // 壁越しにダメージが通らないように遮蔽物を確認する
+      let blocked = rapier_context
+        .cast_ray(event.position, direction, distance, true, filter)
+        .is_some_and(|(hit, _)| hit != entity);
+      if blocked {
+        continue;
+      }
+
+      let falloff = (1.0 - distance / event.radius).clamp(0.0, 1.0);
+
+      if has_health {
+        damage_event_writer.send(DamageEvent {
+          target: entity,
+          amount: event.damage * falloff,
+        });
+      }
+
+      if let Some(RigidBody::Dynamic) = rigid_body {
+        commands.entity(entity).insert(ExternalImpulse {
+          impulse: direction * event.impulse * falloff,
+          ..default()
+        });
+      }
+
+      // プレイヤーは物理演算で動かないので直接速度を加える
+      if let Some(mut player) = player {
+        player.add_impulse(direction * event.impulse * falloff);
+      }
+    }
+  }
+}
@@ -1,7 +1,14 @@
+pub(super) mod explosive;
 pub(super) mod plugin;
+pub(super) mod pool;
 pub(super) mod projectile;
 pub(super) mod raycast;
+pub(super) mod shot;
 
+pub use explosive::*;
 pub use plugin::*;
+pub use pool::*;
 pub use projectile::*;
 // pub use raycast::*;
+pub use raycast::cast_hitscan;
+pub use shot::*;
@@ -1,20 +1,44 @@
-use bevy::prelude::*;
+use bevy::{diagnostic::RegisterDiagnostic, prelude::*};
 
 use super::{
+  explosive::{apply_explosion, update_explosive, ExplosionEvent, Explosive},
+  pool::{bullet_pool_diagnostics, update_bullet_pool_diagnostics, ProjectileBulletPool},
   projectile::{init_projectile, update_projectile, ProjectileBullet, ProjectileBulletAssets},
   raycast::{init_raycast, update_raycast, RaycastBullet, RaycastBulletAssets},
+  shot::{log_shot_hits, update_shots, ShotHitEvent, ShotRegistry},
 };
 
 pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
   fn build(&self, app: &mut App) {
+    for diagnostic in bullet_pool_diagnostics() {
+      app.register_diagnostic(diagnostic);
+    }
+
     app
+      .add_event::<ExplosionEvent>()
+      .add_event::<ShotHitEvent>()
       .add_systems(Startup, (init_raycast, init_projectile))
-      .add_systems(Update, (update_raycast, update_projectile))
+      .add_systems(
+        FixedUpdate,
+        (
+          log_shot_hits.after(update_shots),
+          update_raycast,
+          update_explosive.before(update_projectile),
+          update_projectile,
+          apply_explosion.after(update_explosive),
+          update_shots.after(update_projectile),
+        ),
+      )
+      .add_systems(Update, update_bullet_pool_diagnostics)
       .init_resource::<ProjectileBulletAssets>()
+      .init_resource::<ProjectileBulletPool>()
       .init_resource::<RaycastBulletAssets>()
+      .init_resource::<ShotRegistry>()
+      .register_type::<Explosive>()
       .register_type::<ProjectileBullet>()
+      .register_type::<ProjectileBulletPool>()
       .register_type::<RaycastBullet>();
   }
 }
@@ -0,0 +1,165 @@
+use bevy::{
+  diagnostic::{Diagnostic, DiagnosticPath, Diagnostics},
+  ecs::system::SystemParam,
+  prelude::*,
+};
+
+use crate::game::interpolation::TranslationInterpolation;
+
+use super::{
+  ProjectileBullet, ProjectileBulletAssets, ProjectileBulletBundle, ProjectileBulletGroup,
+};
+
+/// 使っていない弾を置いておく場所
+const PARKING_POSITION: Vec3 = Vec3::new(0.0, -1000.0, 0.0);
+
+/// 待機中の弾の数
+pub const BULLET_POOL_FREE: DiagnosticPath = DiagnosticPath::const_new("bullet_pool/free");
+/// 発射した弾のうち再利用できた割合
+pub const BULLET_POOL_REUSE_RATIO: DiagnosticPath =
+  DiagnosticPath::const_new("bullet_pool/reuse_ratio");
+
+/// 消滅した弾をデスポーンせずに非表示にして再利用する
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct ProjectileBulletPool {
+  /// 待機中の弾
+  free: Vec<Entity>,
+  /// 待機させておける弾の最大数(超えた分はデスポーンする)
+  pub capacity: usize,
+  /// 待機中の弾を再利用した回数
+  pub reused: u64,
+  /// 新しく弾をスポーンした回数
+  pub allocated: u64,
+}
+
+impl Default for ProjectileBulletPool {
+  fn default() -> Self {
+    Self::new(256)
+  }
+}
+
+impl ProjectileBulletPool {
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      free: Vec::with_capacity(capacity),
+      capacity,
+      reused: 0,
+      allocated: 0,
+    }
+  }
+
+  /// 待機中の弾の数
+  pub fn free_count(&self) -> usize {
+    self.free.len()
+  }
+
+  /// 発射した弾のうち再利用できた割合
+  pub fn reuse_ratio(&self) -> f32 {
+    let total = self.reused + self.allocated;
+    if total == 0 {
+      0.0
+    } else {
+      self.reused as f32 / total as f32
+    }
+  }
+
+  /// 待機中の弾があれば再利用し、なければ新しくスポーンする
+  pub fn fire(
+    &mut self,
+    commands: &mut Commands,
+    group: &ProjectileBulletGroup,
+    assets: &ProjectileBulletAssets,
+    transform: Transform,
+    bullet: ProjectileBullet,
+  ) -> Entity {
+    if let Some(entity) = self.free.pop() {
+      // 既にあるコンポーネントを上書きするだけなのでアーキタイプは変わらない
+      commands.entity(entity).insert((
+        transform,
+        TranslationInterpolation::new(transform.translation),
+        bullet,
+        Visibility::Inherited,
+      ));
+      self.reused += 1;
+      entity
+    } else {
+      let mut entity = Entity::PLACEHOLDER;
+      commands.entity(group.0).with_children(|parent| {
+        entity = parent
+          .spawn(
+            ProjectileBulletBundle::new(
+              assets.bullet_mesh.clone(),
+              assets.bullet_material.clone(),
+              transform,
+              bullet.speed,
+              0.0,
+            )
+            .with_bullet(bullet),
+          )
+          .id();
+      });
+      self.allocated += 1;
+      entity
+    }
+  }
+
+  /// 弾を待機させる
+  /// 待機中の弾が`capacity`に達していたらfalseを返す
+  pub(super) fn park(
+    &mut self,
+    entity: Entity,
+    bullet: &mut ProjectileBullet,
+    transform: &mut Transform,
+    visibility: &mut Visibility,
+  ) -> bool {
+    if self.free.len() >= self.capacity {
+      return false;
+    }
+
+    bullet.active = false;
+    transform.translation = PARKING_POSITION;
+    *visibility = Visibility::Hidden;
+    self.free.push(entity);
+    true
+  }
+}
+
+/// プールから弾を発射するのに使うリソース
+#[derive(SystemParam)]
+pub struct ProjectileBulletSpawner<'w> {
+  group: Res<'w, ProjectileBulletGroup>,
+  assets: Res<'w, ProjectileBulletAssets>,
+  pool: ResMut<'w, ProjectileBulletPool>,
+}
+
+impl ProjectileBulletSpawner<'_> {
+  pub fn fire(
+    &mut self,
+    commands: &mut Commands,
+    transform: Transform,
+    bullet: ProjectileBullet,
+  ) -> Entity {
+    self
+      .pool
+      .fire(commands, &self.group, &self.assets, transform, bullet)
+  }
+}
+
+pub(super) fn bullet_pool_diagnostics() -> [Diagnostic; 2] {
+  [
+    Diagnostic::new(BULLET_POOL_FREE),
+    Diagnostic::new(BULLET_POOL_REUSE_RATIO).with_suffix("%"),
+  ]
+}
+
+// 再利用と新しくスポーンした数をDiagnosticsに送る(エディタで確認できる)
+pub(super) fn update_bullet_pool_diagnostics(
+  pool: Res<ProjectileBulletPool>,
+  mut diagnostics: Diagnostics,
+) {
+  diagnostics.add_measurement(&BULLET_POOL_FREE, || pool.free_count() as f64);
+  diagnostics.add_measurement(&BULLET_POOL_REUSE_RATIO, || {
+    pool.reuse_ratio() as f64 * 100.0
+  });
+}
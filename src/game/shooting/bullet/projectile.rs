@@ -1,4 +1,9 @@
 use bevy::{color::palettes::css, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::interpolation::TranslationInterpolation;
+
+use super::{Explosive, ProjectileBulletPool, ShotId, ShotRegistry};
 
 #[derive(Component, Reflect, Debug)]
 pub struct ProjectileBullet {
@@ -6,6 +11,31 @@ pub struct ProjectileBullet {
   pub speed: f32,
   /// 銃弾が消滅するまでの時間
   pub lifetime: Timer,
+  /// falseならプールで待機している
+  pub active: bool,
+  /// 当たったときのダメージ
+  pub damage: f32,
+  /// どの発射で撃たれた弾か(Noneなら何にも当たらない)
+  pub shot: Option<ShotId>,
+}
+
+impl ProjectileBullet {
+  pub fn new(speed: f32, lifetime: f32) -> Self {
+    Self {
+      speed,
+      lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+      active: true,
+      damage: 0.0,
+      shot: None,
+    }
+  }
+
+  /// 当たり判定を持たせ、`ShotRegistry`で集計する
+  pub fn with_shot(mut self, shot: ShotId, damage: f32) -> Self {
+    self.shot = Some(shot);
+    self.damage = damage;
+    self
+  }
 }
 
 #[derive(Bundle)]
@@ -13,6 +43,7 @@ pub struct ProjectileBulletBundle {
   #[bundle()]
   pbr_bundle: PbrBundle,
   projectile_bullet: ProjectileBullet,
+  interpolation: TranslationInterpolation,
 }
 
 impl ProjectileBulletBundle {
@@ -30,12 +61,15 @@ impl ProjectileBulletBundle {
         transform,
         ..Default::default()
       },
-      projectile_bullet: ProjectileBullet {
-        speed,
-        lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
-      },
+      projectile_bullet: ProjectileBullet::new(speed, lifetime),
+      interpolation: TranslationInterpolation::new(transform.translation),
     }
   }
+
+  pub fn with_bullet(mut self, projectile_bullet: ProjectileBullet) -> Self {
+    self.projectile_bullet = projectile_bullet;
+    self
+  }
 }
 
 #[derive(Resource, Debug, Default)]
@@ -70,11 +104,47 @@ pub(super) fn init_projectile(
 pub(super) fn update_projectile(
   mut commands: Commands,
   time: Res<Time>,
-  mut bullet_query: Query<(Entity, &mut ProjectileBullet, &mut Transform)>,
+  rapier_context: Res<RapierContext>,
+  mut pool: ResMut<ProjectileBulletPool>,
+  mut registry: ResMut<ShotRegistry>,
+  mut bullet_query: Query<(
+    Entity,
+    &mut ProjectileBullet,
+    &mut Transform,
+    &mut Visibility,
+    Has<Explosive>,
+  )>,
 ) {
-  for (entity, mut bullet, mut transform) in bullet_query.iter_mut() {
-    if bullet.lifetime.finished() {
-      commands.entity(entity).despawn_recursive();
+  for (entity, mut bullet, mut transform, mut visibility, explosive) in bullet_query.iter_mut() {
+    if !bullet.active {
+      continue;
+    }
+
+    let mut finished = bullet.lifetime.finished();
+
+    if let Some(shot) = bullet.shot {
+      // 次のフレームまでに進む距離だけRaycastする
+      if let Some((target, _)) = rapier_context.cast_ray(
+        transform.translation,
+        *transform.forward(),
+        bullet.speed * time.delta_seconds(),
+        true,
+        QueryFilter::exclude_kinematic().exclude_sensors(),
+      ) {
+        registry.hit(shot, target, bullet.damage);
+        finished = true;
+      }
+
+      if finished {
+        registry.resolve(shot);
+      }
+    }
+
+    if finished {
+      // 爆発物はプールに戻さない
+      if explosive || !pool.park(entity, &mut bullet, &mut transform, &mut visibility) {
+        commands.entity(entity).despawn_recursive();
+      }
     } else {
       let translation = -transform.local_z() * bullet.speed * time.delta_seconds();
       transform.translation += translation;
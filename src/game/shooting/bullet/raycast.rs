@@ -1,4 +1,5 @@
 use bevy::{color::palettes::css, prelude::*};
+use bevy_rapier3d::prelude::*;
 
 // TODO:Bundle化して公開範囲を狭める
 #[derive(Component, Reflect, Debug)]
@@ -39,6 +40,24 @@ pub(super) fn init_raycast(
   commands.insert_resource(RaycastBulletGroup(group));
 }
 
+/// ヒットスキャンで最初に当たったエンティティと位置を返す
+pub fn cast_hitscan(
+  rapier_context: &RapierContext,
+  origin: Vec3,
+  direction: Dir3,
+  range: f32,
+) -> Option<(Entity, Vec3)> {
+  rapier_context
+    .cast_ray(
+      origin,
+      *direction,
+      range,
+      true,
+      QueryFilter::exclude_kinematic().exclude_sensors(),
+    )
+    .map(|(entity, toi)| (entity, origin + *direction * toi))
+}
+
 // TODO:ヒットスキャン
 pub(super) fn update_raycast(
   mut _commands: Commands,
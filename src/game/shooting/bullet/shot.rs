@@ -0,0 +1,108 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::game::health::{DamageEvent, Health};
+
+/// 1回の発射を識別する
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShotId(u32);
+
+/// 1回の発射で体力のある対象に当たった弾(ペレット)のダメージをまとめたもの
+/// ヒットマーカーはこれを読んで表示する
+#[derive(Event, Debug)]
+pub struct ShotHitEvent {
+  pub target: Entity,
+  /// 合計のダメージ
+  pub damage: f32,
+  /// 当たった弾の数
+  pub pellets: u32,
+}
+
+#[derive(Debug, Default)]
+struct Shot {
+  /// まだ当たるか消滅するかしていない弾の数
+  remaining: u32,
+  /// 対象ごとの(ダメージ, 弾の数)
+  hits: HashMap<Entity, (f32, u32)>,
+}
+
+/// 発射中の弾を発射ごとに集計する
+#[derive(Resource, Debug, Default)]
+pub struct ShotRegistry {
+  next_id: u32,
+  shots: HashMap<ShotId, Shot>,
+}
+
+impl ShotRegistry {
+  /// `pellets`発の弾を撃つ発射を始める
+  pub fn begin(&mut self, pellets: u32) -> ShotId {
+    let id = ShotId(self.next_id);
+    self.next_id = self.next_id.wrapping_add(1);
+    self.shots.insert(
+      id,
+      Shot {
+        remaining: pellets,
+        ..default()
+      },
+    );
+    id
+  }
+
+  /// 弾が対象に当たったことを記録する
+  pub fn hit(&mut self, id: ShotId, target: Entity, damage: f32) {
+    if let Some(shot) = self.shots.get_mut(&id) {
+      let (total, pellets) = shot.hits.entry(target).or_default();
+      *total += damage;
+      *pellets += 1;
+    }
+  }
+
+  /// 弾が当たるか消滅して、判定が終わったことを記録する
+  pub fn resolve(&mut self, id: ShotId) {
+    if let Some(shot) = self.shots.get_mut(&id) {
+      shot.remaining = shot.remaining.saturating_sub(1);
+    }
+  }
+}
+
+// 全ての弾の判定が終わった発射のダメージを対象ごとにまとめて与える
+pub(super) fn update_shots(
+  mut registry: ResMut<ShotRegistry>,
+  mut damage_event_writer: EventWriter<DamageEvent>,
+  mut shot_hit_event_writer: EventWriter<ShotHitEvent>,
+  health_query: Query<(), With<Health>>,
+) {
+  registry.shots.retain(|_, shot| {
+    if shot.remaining > 0 {
+      return true;
+    }
+
+    // 壁や地面に当たった弾はダメージもヒットマーカーも出さない
+    for (&target, &(damage, pellets)) in shot.hits.iter() {
+      if !health_query.contains(target) {
+        continue;
+      }
+
+      damage_event_writer.send(DamageEvent {
+        target,
+        amount: damage,
+      });
+      shot_hit_event_writer.send(ShotHitEvent {
+        target,
+        damage,
+        pellets,
+      });
+    }
+
+    false
+  });
+}
+
+// ヒットマーカーができるまではログに出して確かめる
+pub(super) fn log_shot_hits(mut shot_hit_event_reader: EventReader<ShotHitEvent>) {
+  for event in shot_hit_event_reader.read() {
+    debug!(
+      "shot hit {:?}: {} damage from {} pellets",
+      event.target, event.damage, event.pellets
+    );
+  }
+}
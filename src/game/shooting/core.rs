@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::game::player::input::PlayerInput;
+use crate::game::player::input::{FixedInput, PlayerInput};
 
 #[derive(Component, Reflect, Debug, Default)]
 pub struct Shooter {}
@@ -8,11 +8,18 @@ pub struct Shooter {}
 #[derive(Event, Debug)]
 pub struct FireEvent;
 
+/// 近接攻撃(クイック近接)の入力
+/// リロードなどの動作はこれを読んで中断する
+#[derive(Event, Debug)]
+pub struct MeleeEvent;
+
 pub(super) fn init_shooter(mut _commands: Commands) {}
 
 pub(super) fn update_shooter(
-  mouse_input: Res<ButtonInput<MouseButton>>,
+  mouse_input: Res<FixedInput<MouseButton>>,
+  keyboard_input: Res<FixedInput<KeyCode>>,
   mut fire_event_writer: EventWriter<FireEvent>,
+  mut melee_event_writer: EventWriter<MeleeEvent>,
   input: Res<PlayerInput>,
   shooter_query: Query<&Shooter>,
 ) {
@@ -22,5 +29,9 @@ pub(super) fn update_shooter(
       // でも状況によって銃を撃てないようにできたほうがいい…？
       fire_event_writer.send(FireEvent);
     }
+
+    if keyboard_input.just_pressed(input.melee) {
+      melee_event_writer.send(MeleeEvent);
+    }
   }
 }
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  health::{DamageEvent, Health},
+  interpolation::SimulationTransforms,
+  inventory::CurrentWeapon,
+  player::{camera_controller::CameraController, Player},
+};
+
+use super::{FireEvent, MeleeEvent, Shooter};
+
+/// `Shooter`に付けるとクイック近接、アイテムに付けると近接武器になる
+#[derive(Component, Reflect, Debug)]
+pub struct Melee {
+  pub damage: f32,
+  /// 当たった物体に加えるインパルス
+  pub knockback: f32,
+  /// 攻撃が届く距離
+  pub range: f32,
+  /// 判定に使う球の半径
+  pub radius: f32,
+  /// 振る角度(ラジアン)
+  pub arc: f32,
+  /// 振っている時間
+  pub swing: Timer,
+  pub cool_time: Timer,
+  /// 今回の振りで既に当たったか
+  pub hit: bool,
+}
+
+impl Melee {
+  pub fn new(damage: f32, knockback: f32, range: f32, swing_time: f32, cool_time: f32) -> Self {
+    let mut swing = Timer::from_seconds(swing_time, TimerMode::Once);
+    // 振っていない状態にする
+    swing.tick(Duration::from_secs_f32(swing_time));
+
+    Self {
+      damage,
+      knockback,
+      range,
+      radius: 0.4,
+      arc: 90_f32.to_radians(),
+      swing,
+      cool_time: Timer::from_seconds(cool_time, TimerMode::Once),
+      hit: false,
+    }
+  }
+}
+
+/// `update_melee`で読み書きするイベント
+#[derive(SystemParam)]
+pub(super) struct MeleeEvents<'w, 's> {
+  fire_event_reader: EventReader<'w, 's, FireEvent>,
+  melee_event_reader: EventReader<'w, 's, MeleeEvent>,
+  damage_event_writer: EventWriter<'w, DamageEvent>,
+}
+
+/// なぎ払う位置と向きを求めるのに使うカメラ
+#[derive(SystemParam)]
+pub(super) struct MeleeCamera<'w, 's> {
+  camera_query: Query<'w, 's, (Entity, &'static Parent), With<CameraController>>,
+  transforms: SimulationTransforms<'w, 's>,
+}
+
+// 振っている間はカメラの前をshape castでなぎ払い、最初に当たったものにダメージを与える
+pub(super) fn update_melee(
+  mut commands: Commands,
+  mut events: MeleeEvents,
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  melee_camera: MeleeCamera,
+  mut melee_query: Query<(&mut Melee, Has<Shooter>, Has<CurrentWeapon>)>,
+  mut target_query: Query<(Option<&RigidBody>, Has<Health>, Option<&mut Player>)>,
+) {
+  let fired = events.fire_event_reader.read().count() > 0;
+  let bashed = events.melee_event_reader.read().count() > 0;
+
+  // カメラの親は攻撃しているプレイヤー
+  let Ok((camera, attacker)) = melee_camera.camera_query.get_single() else {
+    return;
+  };
+  let Some(camera) = melee_camera.transforms.get(camera) else {
+    return;
+  };
+
+  for (mut melee, is_shooter, is_current_weapon) in melee_query.iter_mut() {
+    melee.cool_time.tick(time.delta());
+
+    let triggered = (is_shooter && bashed) || (is_current_weapon && fired);
+    if triggered && melee.cool_time.finished() && melee.swing.finished() {
+      melee.swing.reset();
+      melee.cool_time.reset();
+      melee.hit = false;
+    }
+
+    if melee.swing.finished() {
+      continue;
+    }
+    melee.swing.tick(time.delta());
+
+    if melee.hit {
+      continue;
+    }
+
+    // 右から左へなぎ払う
+    let yaw = (melee.arc * 0.5).lerp(-melee.arc * 0.5, melee.swing.fraction());
+    let direction = Quat::from_axis_angle(*camera.up(), yaw) * *camera.forward();
+
+    if let Some((entity, _)) = rapier_context.cast_shape(
+      camera.translation,
+      Quat::IDENTITY,
+      direction,
+      &Collider::ball(melee.radius),
+      ShapeCastOptions::with_max_time_of_impact(melee.range),
+      QueryFilter::default()
+        .exclude_collider(attacker.get())
+        .exclude_sensors(),
+    ) {
+      melee.hit = true;
+
+      let Ok((rigid_body, has_health, player)) = target_query.get_mut(entity) else {
+        continue;
+      };
+
+      if has_health {
+        events.damage_event_writer.send(DamageEvent {
+          target: entity,
+          amount: melee.damage,
+        });
+      }
+
+      if let Some(RigidBody::Dynamic) = rigid_body {
+        commands.entity(entity).insert(ExternalImpulse {
+          impulse: direction * melee.knockback,
+          ..default()
+        });
+      }
+
+      if let Some(mut player) = player {
+        player.add_impulse(direction * melee.knockback);
+      }
+    }
+  }
+}
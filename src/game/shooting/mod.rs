@@ -1,7 +1,9 @@
 pub(super) mod bullet;
 pub(super) mod core;
+pub(super) mod melee;
 pub(super) mod plugin;
 pub(super) mod weapons;
 
 pub use core::*;
+pub use melee::*;
 pub use plugin::*;
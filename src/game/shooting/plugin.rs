@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 
 use super::{
-  bullet::BulletPlugin, init_shooter, update_shooter, weapons::WeaponPlugin, FireEvent, Shooter,
+  bullet::BulletPlugin, init_shooter, update_melee, update_shooter, weapons::WeaponPlugin,
+  FireEvent, Melee, MeleeEvent, Shooter,
 };
 
 pub struct ShootingPlugin;
@@ -10,9 +11,14 @@ impl Plugin for ShootingPlugin {
   fn build(&self, app: &mut App) {
     app
       .add_event::<FireEvent>()
+      .add_event::<MeleeEvent>()
       .add_plugins((BulletPlugin, WeaponPlugin))
       .add_systems(Startup, init_shooter)
-      .add_systems(Update, update_shooter)
+      .add_systems(
+        FixedUpdate,
+        (update_shooter, update_melee.after(update_shooter)),
+      )
+      .register_type::<Melee>()
       .register_type::<Shooter>();
   }
 }
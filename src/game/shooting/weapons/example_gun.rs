@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 
 use crate::game::{
+  interpolation::SimulationTransforms,
   inventory::CurrentWeapon,
   shooting::{
-    bullet::{ProjectileBulletAssets, ProjectileBulletBundle, ProjectileBulletGroup},
+    bullet::{ProjectileBullet, ProjectileBulletSpawner},
     FireEvent,
   },
 };
@@ -19,23 +20,23 @@ pub(super) fn update(
   mut commands: Commands,
   mut fire_event_reader: EventReader<FireEvent>,
   time: Res<Time>,
-  group: Res<ProjectileBulletGroup>,
-  assets: Res<ProjectileBulletAssets>,
-  mut gun: Query<(&mut ExampleGun, &GlobalTransform), With<CurrentWeapon>>,
+  mut spawner: ProjectileBulletSpawner,
+  transforms: SimulationTransforms,
+  mut gun: Query<(Entity, &mut ExampleGun), With<CurrentWeapon>>,
 ) {
-  for (mut gun, global_transform) in gun.iter_mut() {
+  for (entity, mut gun) in gun.iter_mut() {
     for _ in fire_event_reader.read() {
       if gun.cool_time.finished() {
+        let Some(transform) = transforms.get(entity) else {
+          continue;
+        };
+
         // TODO:弾の発射処理はbulletの実装に移し、イベントで発火させる
-        commands.entity(group.0).with_children(|parent| {
-          parent.spawn(ProjectileBulletBundle::new(
-            assets.bullet_mesh.clone(),
-            assets.bullet_material.clone(),
-            global_transform.compute_transform(),
-            gun.bullet_speed,
-            gun.bullet_lifetime,
-          ));
-        });
+        spawner.fire(
+          &mut commands,
+          transform,
+          ProjectileBullet::new(gun.bullet_speed, gun.bullet_lifetime),
+        );
 
         gun.cool_time.reset();
       }
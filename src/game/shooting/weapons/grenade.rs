@@ -0,0 +1,210 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  interpolation::SimulationTransforms,
+  inventory::CurrentWeapon,
+  shooting::{
+    bullet::{ExplosionEvent, Explosive},
+    FireEvent,
+  },
+};
+
+/// 弾道予測の1ステップの時間
+const PREDICTION_STEP: f32 = 1.0 / 60.0;
+/// 弾道予測で跳ね返りを計算する最大の回数
+const PREDICTION_MAX_BOUNCES: usize = 4;
+
+#[derive(Component, Reflect, Debug)]
+pub struct Grenade {
+  pub cool_time: Timer,
+  /// 信管が作動してから爆発するまでの時間
+  pub fuse: f32,
+  /// 握り始めてからの時間(Noneなら握っていない)
+  pub held: Option<f32>,
+  /// すぐに投げたときの強さ(m/sec)
+  pub min_strength: f32,
+  /// 最も強く投げたときの強さ(m/sec)
+  pub max_strength: f32,
+  /// max_strengthになるまで握る時間
+  pub charge_time: f32,
+  /// グレネードの半径
+  pub radius: f32,
+  /// 反発係数
+  pub restitution: f32,
+  /// 爆発の半径
+  pub explosion_radius: f32,
+  /// 爆心でのダメージ
+  pub explosion_damage: f32,
+  /// 爆心でのインパルス
+  pub explosion_impulse: f32,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct GrenadeAssets {
+  /// 半径1.0の球(`Grenade::radius`で拡大縮小する)
+  pub mesh: Handle<Mesh>,
+  pub material: Handle<StandardMaterial>,
+}
+
+impl Grenade {
+  /// 握っている時間から投げる速度を求める
+  pub fn throw_velocity(&self, direction: Vec3) -> Vec3 {
+    let charge = (self.held.unwrap_or_default() / self.charge_time).clamp(0.0, 1.0);
+    direction * self.min_strength.lerp(self.max_strength, charge)
+  }
+
+  /// 爆発するまでの残り時間
+  pub fn remaining_fuse(&self) -> f32 {
+    (self.fuse - self.held.unwrap_or_default()).max(0.0)
+  }
+}
+
+pub(super) fn init(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+  commands.insert_resource(GrenadeAssets {
+    mesh: meshes.add(Sphere::new(1.0)),
+    material: materials.add(Color::Srgba(css::DARK_OLIVEGREEN)),
+  });
+}
+
+// 押している間は信管を作動させ、離したら投げる
+pub(super) fn update(
+  mut commands: Commands,
+  mut fire_event_reader: EventReader<FireEvent>,
+  mut explosion_event_writer: EventWriter<ExplosionEvent>,
+  time: Res<Time>,
+  assets: Res<GrenadeAssets>,
+  transforms: SimulationTransforms,
+  mut grenade_query: Query<(Entity, &mut Grenade), With<CurrentWeapon>>,
+) {
+  let fired = fire_event_reader.read().count() > 0;
+
+  for (entity, mut grenade) in grenade_query.iter_mut() {
+    grenade.cool_time.tick(time.delta());
+    let Some(transform) = transforms.get(entity) else {
+      continue;
+    };
+
+    match (fired, grenade.held) {
+      (true, None) if grenade.cool_time.finished() => {
+        grenade.held = Some(0.0);
+      }
+      (true, Some(held)) => {
+        let held = held + time.delta_seconds();
+        grenade.held = Some(held);
+
+        // 握ったまま信管が切れたら手元で爆発する
+        if held >= grenade.fuse {
+          explosion_event_writer.send(ExplosionEvent {
+            source: None,
+            position: transform.translation,
+            radius: grenade.explosion_radius,
+            damage: grenade.explosion_damage,
+            impulse: grenade.explosion_impulse,
+          });
+          grenade.held = None;
+          grenade.cool_time.reset();
+        }
+      }
+      (false, Some(_)) => {
+        let velocity = grenade.throw_velocity(*transform.forward());
+
+        commands.spawn((
+          Name::new("Grenade"),
+          PbrBundle {
+            mesh: assets.mesh.clone(),
+            material: assets.material.clone(),
+            transform: Transform::from_translation(transform.translation)
+              .with_scale(Vec3::splat(grenade.radius)),
+            ..default()
+          },
+          RigidBody::Dynamic,
+          Collider::ball(1.0),
+          Restitution {
+            coefficient: grenade.restitution,
+            combine_rule: CoefficientCombineRule::Max,
+          },
+          Velocity::linear(velocity),
+          Ccd::enabled(),
+          Explosive {
+            radius: grenade.explosion_radius,
+            damage: grenade.explosion_damage,
+            impulse: grenade.explosion_impulse,
+            fuse: Some(Timer::from_seconds(
+              grenade.remaining_fuse(),
+              TimerMode::Once,
+            )),
+            on_impact: false,
+          },
+        ));
+
+        grenade.held = None;
+        grenade.cool_time.reset();
+      }
+      _ => {}
+    }
+  }
+}
+
+// 握っている間は投げたときの軌道を表示する
+// 壁での跳ね返りも物理エンジンと同じ重力と反発係数で予測する
+pub(super) fn update_trajectory_preview(
+  mut gizmos: Gizmos,
+  rapier_config: Res<RapierConfiguration>,
+  rapier_context: Res<RapierContext>,
+  grenade_query: Query<(&Grenade, &GlobalTransform), With<CurrentWeapon>>,
+) {
+  for (grenade, global_transform) in grenade_query.iter() {
+    if grenade.held.is_none() {
+      continue;
+    }
+
+    let mut position = global_transform.translation();
+    let mut velocity = grenade.throw_velocity(*global_transform.forward());
+    let mut points = vec![position];
+    let mut bounces = 0;
+    let mut elapsed = 0.0;
+
+    while elapsed < grenade.remaining_fuse() {
+      let step = velocity * PREDICTION_STEP;
+      let hit = step.try_normalize().and_then(|direction| {
+        rapier_context.cast_ray_and_get_normal(
+          position,
+          direction,
+          step.length() + grenade.radius,
+          true,
+          QueryFilter::exclude_kinematic().exclude_sensors(),
+        )
+      });
+
+      if let Some((_, intersection)) = hit {
+        position = intersection.point + intersection.normal * grenade.radius;
+        velocity -=
+          (1.0 + grenade.restitution) * velocity.dot(intersection.normal) * intersection.normal;
+        bounces += 1;
+      } else {
+        position += step;
+      }
+
+      velocity += rapier_config.gravity * PREDICTION_STEP;
+      elapsed += PREDICTION_STEP;
+      points.push(position);
+
+      if bounces > PREDICTION_MAX_BOUNCES {
+        break;
+      }
+    }
+
+    gizmos.linestrip(points, css::YELLOW);
+    gizmos.sphere(
+      position,
+      Quat::IDENTITY,
+      grenade.explosion_radius,
+      css::ORANGE,
+    );
+  }
+}
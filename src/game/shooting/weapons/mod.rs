@@ -1,9 +1,15 @@
 pub(super) mod core;
 pub(super) mod example_gun;
+pub(super) mod grenade;
 pub(super) mod mox_47;
 pub(super) mod plugin;
+pub(super) mod rocket_launcher;
+pub(super) mod shotgun;
 
 // pub use core::*;
 pub use example_gun::ExampleGun;
+pub use grenade::Grenade;
 pub use mox_47::Mox47;
 pub use plugin::*;
+pub use rocket_launcher::RocketLauncher;
+pub use shotgun::{Shotgun, SpreadPattern};
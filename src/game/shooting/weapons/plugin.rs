@@ -2,17 +2,33 @@ use bevy::prelude::*;
 
 use crate::game::shooting::update_shooter;
 
-use super::{example_gun, mox_47, ExampleGun};
+use super::{
+  example_gun, grenade, grenade::GrenadeAssets, mox_47, rocket_launcher, shotgun, ExampleGun,
+  Grenade, RocketLauncher, Shotgun,
+};
 
 pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
   fn build(&self, app: &mut App) {
     app
+      .add_systems(Startup, grenade::init)
       .add_systems(
-        Update,
-        (example_gun::update, mox_47::update).after(update_shooter),
+        FixedUpdate,
+        (
+          example_gun::update,
+          grenade::update,
+          mox_47::update,
+          rocket_launcher::update,
+          shotgun::update,
+        )
+          .after(update_shooter),
       )
-      .register_type::<ExampleGun>();
+      .add_systems(Update, grenade::update_trajectory_preview)
+      .init_resource::<GrenadeAssets>()
+      .register_type::<ExampleGun>()
+      .register_type::<Grenade>()
+      .register_type::<RocketLauncher>()
+      .register_type::<Shotgun>();
   }
 }
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::game::{
+  interpolation::SimulationTransforms,
+  inventory::CurrentWeapon,
+  shooting::{
+    bullet::{Explosive, ProjectileBulletAssets, ProjectileBulletBundle, ProjectileBulletGroup},
+    FireEvent,
+  },
+};
+
+#[derive(Component, Reflect, Debug)]
+pub struct RocketLauncher {
+  pub cool_time: Timer,
+  pub bullet_speed: f32,
+  pub bullet_lifetime: f32,
+  /// 爆発の半径
+  pub explosion_radius: f32,
+  /// 爆心でのダメージ
+  pub explosion_damage: f32,
+  /// 爆心でのインパルス
+  pub explosion_impulse: f32,
+}
+
+pub(super) fn update(
+  mut commands: Commands,
+  mut fire_event_reader: EventReader<FireEvent>,
+  time: Res<Time>,
+  group: Res<ProjectileBulletGroup>,
+  assets: Res<ProjectileBulletAssets>,
+  transforms: SimulationTransforms,
+  mut gun: Query<(Entity, &mut RocketLauncher), With<CurrentWeapon>>,
+) {
+  for (entity, mut gun) in gun.iter_mut() {
+    for _ in fire_event_reader.read() {
+      if gun.cool_time.finished() {
+        let Some(transform) = transforms.get(entity) else {
+          continue;
+        };
+
+        commands.entity(group.0).with_children(|parent| {
+          parent.spawn((
+            ProjectileBulletBundle::new(
+              assets.bullet_mesh.clone(),
+              assets.bullet_material.clone(),
+              transform,
+              gun.bullet_speed,
+              gun.bullet_lifetime,
+            ),
+            Explosive {
+              radius: gun.explosion_radius,
+              damage: gun.explosion_damage,
+              impulse: gun.explosion_impulse,
+              fuse: None,
+              on_impact: true,
+            },
+          ));
+        });
+
+        gun.cool_time.reset();
+      }
+    }
+
+    gun.cool_time.tick(time.delta());
+  }
+}
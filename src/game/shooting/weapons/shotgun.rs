@@ -0,0 +1,132 @@
+use std::f32::consts::TAU;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+use crate::game::{
+  interpolation::SimulationTransforms,
+  inventory::CurrentWeapon,
+  shooting::{
+    bullet::{cast_hitscan, ProjectileBullet, ProjectileBulletSpawner, ShotRegistry},
+    FireEvent,
+  },
+};
+
+#[derive(Reflect, Debug, Clone)]
+pub enum SpreadPattern {
+  /// 円錐の中にランダムに散らばる(円錐の半角、ラジアン)
+  Cone(f32),
+  /// 決まった位置に散らばる(x:右、y:上への角度、ラジアン)
+  /// 弾の数が多い場合は繰り返す
+  Fixed(Vec<Vec2>),
+}
+
+impl SpreadPattern {
+  /// 弾ごとの発射方向のずれを返す
+  pub fn offsets(&self, pellets: u32, seed: &mut u32) -> Vec<Vec2> {
+    match self {
+      SpreadPattern::Cone(half_angle) => (0..pellets)
+        .map(|_| {
+          // 円錐の断面に均一に散らばるようにする
+          let radius = random(seed).sqrt() * half_angle;
+          let theta = random(seed) * TAU;
+          Vec2::new(radius * theta.cos(), radius * theta.sin())
+        })
+        .collect(),
+      SpreadPattern::Fixed(pattern) if !pattern.is_empty() => pattern
+        .iter()
+        .cycle()
+        .take(pellets as usize)
+        .copied()
+        .collect(),
+      SpreadPattern::Fixed(_) => vec![Vec2::ZERO; pellets as usize],
+    }
+  }
+}
+
+/// xorshiftで0.0..1.0の乱数を返す
+fn random(seed: &mut u32) -> f32 {
+  *seed ^= *seed << 13;
+  *seed ^= *seed >> 17;
+  *seed ^= *seed << 5;
+  (*seed >> 8) as f32 / (1 << 24) as f32
+}
+
+#[derive(Component, Reflect, Debug)]
+pub struct Shotgun {
+  pub cool_time: Timer,
+  /// 1回の発射で撃つ弾の数
+  pub pellets: u32,
+  pub spread: SpreadPattern,
+  /// 弾1発のダメージ
+  pub pellet_damage: f32,
+  /// trueならヒットスキャン、falseなら弾を飛ばす
+  pub hitscan: bool,
+  /// ヒットスキャンの射程
+  pub range: f32,
+  pub bullet_speed: f32,
+  pub bullet_lifetime: f32,
+  /// 散らばりの乱数の状態(0以外)
+  pub seed: u32,
+}
+
+/// 弾を飛ばしてダメージを集計するのに使うリソース
+#[derive(SystemParam)]
+pub(super) struct ShotgunBullets<'w> {
+  spawner: ProjectileBulletSpawner<'w>,
+  registry: ResMut<'w, ShotRegistry>,
+}
+
+pub(super) fn update(
+  mut commands: Commands,
+  mut fire_event_reader: EventReader<FireEvent>,
+  time: Res<Time>,
+  rapier_context: Res<RapierContext>,
+  mut bullets: ShotgunBullets,
+  transforms: SimulationTransforms,
+  mut gun: Query<(Entity, &mut Shotgun), With<CurrentWeapon>>,
+) {
+  for (entity, mut gun) in gun.iter_mut() {
+    for _ in fire_event_reader.read() {
+      if gun.cool_time.finished() {
+        let Some(transform) = transforms.get(entity) else {
+          continue;
+        };
+        let shot = bullets.registry.begin(gun.pellets);
+
+        let mut seed = gun.seed;
+        let offsets = gun.spread.offsets(gun.pellets, &mut seed);
+        gun.seed = seed;
+
+        for offset in offsets {
+          let pellet_transform = transform.with_rotation(
+            transform.rotation * Quat::from_euler(EulerRot::YXZ, -offset.x, offset.y, 0.0),
+          );
+
+          if gun.hitscan {
+            if let Some((target, _)) = cast_hitscan(
+              &rapier_context,
+              pellet_transform.translation,
+              pellet_transform.forward(),
+              gun.range,
+            ) {
+              bullets.registry.hit(shot, target, gun.pellet_damage);
+            }
+            bullets.registry.resolve(shot);
+          } else {
+            bullets.spawner.fire(
+              &mut commands,
+              pellet_transform,
+              ProjectileBullet::new(gun.bullet_speed, gun.bullet_lifetime)
+                .with_shot(shot, gun.pellet_damage),
+            );
+          }
+        }
+
+        gun.cool_time.reset();
+      }
+    }
+
+    gun.cool_time.tick(time.delta());
+  }
+}
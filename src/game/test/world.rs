@@ -48,6 +48,11 @@ pub(super) fn generate_collider(
 
     println!("{} {}", entity.index(), de.collider.is_some());
 
+    // はしごやジャンプ台などはそれぞれのシステムでSensorのコライダーを作る
+    if de.collider.is_none() {
+      continue;
+    }
+
     // let option = ComputedColliderShape::ConvexDecomposition(VHACDParameters {
     //   concavity: 0.01,
     //   resolution: 64,
@@ -1,11 +1,20 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
+use crate::game::health::Health;
+
+use super::{BoostZone, GravityZone, JumpPad, Teleporter, TriggerBundle};
+
 #[derive(Bundle)]
 pub(super) struct BoxBundle {
   name: Name,
   collider: Collider,
   rigid_body: RigidBody,
+  health: Health,
+  // 浮力(`update_buoyancy`)に使う
+  mass_properties: ReadMassProperties,
+  external_force: ExternalForce,
+  damping: Damping,
 }
 
 impl Default for BoxBundle {
@@ -14,6 +23,10 @@ impl Default for BoxBundle {
       name: Name::new("Box"),
       collider: Collider::cuboid(1.0, 1.0, 1.0),
       rigid_body: RigidBody::Dynamic,
+      health: Health::new(100.0),
+      mass_properties: ReadMassProperties::default(),
+      external_force: ExternalForce::default(),
+      damping: Damping::default(),
     }
   }
 }
@@ -108,4 +121,171 @@ pub(super) fn init_world(
         ..default()
       });
     });
+
+  // Stairs(段差の高さを変えた階段と、登れない高さの段差)
+  let step_material = materials.add(Color::srgb_u8(200, 200, 200));
+  for (row, step_height) in [0.15_f32, 0.25, 0.35].into_iter().enumerate() {
+    for step in 0..8 {
+      let height = step_height * (step + 1) as f32;
+      commands.spawn((
+        Name::new(format!("Stairs {row}-{step}")),
+        Collider::cuboid(1.5, height / 2.0, 0.2),
+        PbrBundle {
+          mesh: meshes.add(Cuboid::new(3.0, height, 0.4)),
+          material: step_material.clone(),
+          transform: Transform::from_xyz(
+            20.0 + row as f32 * 4.0,
+            height / 2.0,
+            20.0 - step as f32 * 0.4,
+          ),
+          ..default()
+        },
+      ));
+    }
+  }
+  commands.spawn((
+    Name::new("Ledge"),
+    Collider::cuboid(1.5, 0.25, 1.5),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(3.0, 0.5, 3.0)),
+      material: step_material,
+      transform: Transform::from_xyz(32.0, 0.25, 18.0),
+      ..default()
+    },
+  ));
+
+  // JumpPad
+  commands.spawn((
+    TriggerBundle::new(
+      "JumpPad",
+      JumpPad {
+        velocity: Vec3::new(0.0, 20.0, -10.0),
+      },
+      Vec3::new(1.5, 0.25, 1.5),
+    ),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(3.0, 0.5, 3.0)),
+      material: materials.add(Color::srgb_u8(0, 255, 127)),
+      transform: Transform::from_xyz(-10.0, 0.25, -40.0),
+      ..default()
+    },
+  ));
+
+  // BoostZone
+  commands.spawn((
+    TriggerBundle::new(
+      "BoostZone",
+      BoostZone { multiplier: 2.0 },
+      Vec3::new(2.0, 2.0, 8.0),
+    ),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(4.0, 4.0, 16.0)),
+      material: materials.add(StandardMaterial {
+        base_color: Color::srgba_u8(255, 200, 0, 64),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+      }),
+      transform: Transform::from_xyz(0.0, 2.0, -40.0),
+      ..default()
+    },
+  ));
+
+  // GravityZone(壁の手前に入ると壁を歩ける)
+  commands.spawn((
+    Name::new("GravityWall"),
+    Collider::cuboid(0.5, 10.0, 10.0),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(1.0, 20.0, 20.0)),
+      material: materials.add(Color::srgb_u8(0, 127, 255)),
+      transform: Transform::from_xyz(-40.5, 10.0, -40.0),
+      ..default()
+    },
+  ));
+  commands.spawn((
+    TriggerBundle::new(
+      "WallGravityZone",
+      GravityZone {
+        direction: Vec3::NEG_X,
+        strength: 1.0,
+      },
+      Vec3::new(3.0, 10.0, 10.0),
+    ),
+    TransformBundle::from_transform(Transform::from_xyz(-37.0, 10.0, -40.0)),
+  ));
+
+  // GravityZone(重力が弱い)
+  commands.spawn((
+    TriggerBundle::new(
+      "LowGravityZone",
+      GravityZone {
+        direction: Vec3::NEG_Y,
+        strength: 0.3,
+      },
+      Vec3::new(5.0, 10.0, 5.0),
+    ),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(10.0, 20.0, 10.0)),
+      material: materials.add(StandardMaterial {
+        base_color: Color::srgba_u8(0, 127, 255, 32),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+      }),
+      transform: Transform::from_xyz(-20.0, 10.0, -40.0),
+      ..default()
+    },
+  ));
+
+  // Teleporter(行き先同士を後から繋ぐ)
+  let teleporter_mesh = meshes.add(Cuboid::new(2.0, 3.0, 0.5));
+  let teleporter_material = materials.add(Color::srgb_u8(127, 0, 255));
+  let mut spawn_teleporter = |name: &str, transform: Transform| {
+    commands
+      .spawn((
+        Name::new(name.to_string()),
+        Sensor,
+        Collider::cuboid(1.0, 1.5, 0.25),
+        PbrBundle {
+          mesh: teleporter_mesh.clone(),
+          material: teleporter_material.clone(),
+          transform,
+          ..default()
+        },
+      ))
+      .id()
+  };
+  let teleporter_a = spawn_teleporter("TeleporterA", Transform::from_xyz(10.0, 1.5, -40.0));
+  let teleporter_b = spawn_teleporter(
+    "TeleporterB",
+    Transform::from_xyz(40.0, 1.5, -60.0).with_rotation(Quat::from_rotation_y(90_f32.to_radians())),
+  );
+  commands.entity(teleporter_a).insert(Teleporter {
+    destination: teleporter_b,
+  });
+  commands.entity(teleporter_b).insert(Teleporter {
+    destination: teleporter_a,
+  });
+}
+
+/// glTFのノードの子のメッシュからSensorのコライダーを作り、bundleを一緒に付ける
+/// プレイヤーは当たったコライダーのエンティティでbundleを探すので同じエンティティに付ける
+pub(super) fn insert_sensor_colliders(
+  commands: &mut Commands,
+  meshes: &Assets<Mesh>,
+  children: &Children,
+  mesh_query: &Query<&Handle<Mesh>, Without<Collider>>,
+  bundle: impl Bundle + Clone,
+) {
+  // 範囲はメッシュの凸包にする
+  for &child in children.iter() {
+    if let Some(collider) = mesh_query
+      .get(child)
+      .ok()
+      .and_then(|mesh| meshes.get(mesh))
+      .and_then(|mesh| Collider::from_bevy_mesh(mesh, &ComputedColliderShape::ConvexHull))
+    {
+      commands
+        .entity(child)
+        .insert((collider, Sensor, bundle.clone()));
+    }
+  }
 }
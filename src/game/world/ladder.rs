@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::insert_sensor_colliders;
+
+/// プレイヤーが登れる範囲(Sensorのコライダーと一緒に使う)
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct Ladder {
+  /// 登る速さ
+  pub climb_speed: f32,
+}
+
+impl Default for Ladder {
+  fn default() -> Self {
+    Self { climb_speed: 4.0 }
+  }
+}
+
+#[derive(Deserialize, Debug)]
+struct SerDeLadder {
+  /// Blenderのカスタムプロパティ
+  /// 数値なら登る速さとして使う
+  ladder: Option<Value>,
+}
+
+// カスタムプロパティ`ladder`を持つglTFのノードをはしごにする
+pub(super) fn generate_ladder(
+  mut commands: Commands,
+  meshes: Res<Assets<Mesh>>,
+  query: Query<(&GltfExtras, &Children), Added<GltfExtras>>,
+  mesh_query: Query<&Handle<Mesh>, Without<Collider>>,
+) {
+  for (gltf_extras, children) in query.iter() {
+    let Ok(SerDeLadder {
+      ladder: Some(ladder),
+    }) = serde_json::from_str::<SerDeLadder>(&gltf_extras.value)
+    else {
+      continue;
+    };
+
+    let mut ladder_component = Ladder::default();
+    if let Some(climb_speed) = ladder.as_f64() {
+      ladder_component.climb_speed = climb_speed as f32;
+    }
+    insert_sensor_colliders(
+      &mut commands,
+      &meshes,
+      children,
+      &mesh_query,
+      ladder_component,
+    );
+  }
+}
+
+// はしごのテストコース
+pub(super) fn init_ladders(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+  const HEIGHT: f32 = 8.0;
+
+  // 登る先の壁
+  commands.spawn((
+    Name::new("Ladder Wall"),
+    Collider::cuboid(3.0, HEIGHT / 2.0, 0.5),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(6.0, HEIGHT, 1.0)),
+      material: materials.add(Color::srgb_u8(120, 120, 120)),
+      transform: Transform::from_xyz(20.0, HEIGHT / 2.0, -20.0),
+      ..default()
+    },
+  ));
+
+  commands.spawn((
+    Name::new("Ladder"),
+    Ladder::default(),
+    Sensor,
+    Collider::cuboid(0.6, HEIGHT / 2.0, 0.4),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(1.2, HEIGHT, 0.1)),
+      material: materials.add(Color::srgb_u8(160, 100, 40)),
+      transform: Transform::from_xyz(20.0, HEIGHT / 2.0, -19.4),
+      ..default()
+    },
+  ));
+}
@@ -1,5 +1,13 @@
 pub(super) mod core;
+pub(super) mod ladder;
+pub(super) mod platform;
 pub(super) mod plugin;
+pub(super) mod trigger;
+pub(super) mod water;
 
 use core::*;
+pub use ladder::*;
+pub use platform::*;
 pub use plugin::*;
+pub use trigger::*;
+pub use water::*;
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game::interpolation::TranslationInterpolation;
+
+#[derive(Reflect, Debug, Clone)]
+pub enum PlatformMotion {
+  /// fromとtoの間を往復する
+  Linear { from: Vec3, to: Vec3, speed: f32 },
+  /// Y軸で回転する(rad/sec)
+  Rotating { angular_speed: f32 },
+  /// 動かずに表面に乗ったものを運ぶ(ベルトコンベア)
+  Conveyor { velocity: Vec3 },
+}
+
+/// 乗ったプレイヤーを運ぶ足場
+#[derive(Component, Reflect, Debug)]
+pub struct Platform {
+  pub motion: PlatformMotion,
+  /// 往復の進み具合(0.0..2.0、Linearのみ)
+  pub progress: f32,
+  /// 現在の速度
+  pub linear_velocity: Vec3,
+  /// 現在の角速度
+  pub angular_velocity: Vec3,
+}
+
+impl Platform {
+  pub fn new(motion: PlatformMotion) -> Self {
+    Self {
+      motion,
+      progress: 0.0,
+      linear_velocity: Vec3::ZERO,
+      angular_velocity: Vec3::ZERO,
+    }
+  }
+
+  /// 足場の上のpointの速度
+  pub fn velocity_at(&self, center: Vec3, point: Vec3) -> Vec3 {
+    self.linear_velocity + self.angular_velocity.cross(point - center)
+  }
+}
+
+pub(super) fn update_platforms(
+  time: Res<Time>,
+  mut platform_query: Query<(&mut Platform, &mut Transform)>,
+) {
+  let delta = time.delta_seconds();
+  if delta <= 0.0 {
+    return;
+  }
+
+  for (mut platform, mut transform) in platform_query.iter_mut() {
+    match platform.motion.clone() {
+      PlatformMotion::Linear { from, to, speed } => {
+        let distance = from.distance(to);
+        if distance <= 0.0 {
+          continue;
+        }
+
+        platform.progress = (platform.progress + speed * delta / distance) % 2.0;
+        // 0.0..1.0で行き、1.0..2.0で戻る
+        let t = 1.0 - (platform.progress - 1.0).abs();
+        let next = from.lerp(to, t);
+
+        platform.linear_velocity = (next - transform.translation) / delta;
+        transform.translation = next;
+      }
+      PlatformMotion::Rotating { angular_speed } => {
+        transform.rotate_y(angular_speed * delta);
+        platform.angular_velocity = Vec3::Y * angular_speed;
+      }
+      PlatformMotion::Conveyor { velocity } => {
+        platform.linear_velocity = transform.rotation * velocity;
+      }
+    }
+  }
+}
+
+// 動く足場のテストコース
+pub(super) fn init_platforms(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+  let material = materials.add(Color::srgb_u8(0, 160, 255));
+
+  // 往復する足場
+  let from = Vec3::new(-20.0, 1.0, 0.0);
+  commands.spawn((
+    Name::new("Linear Platform"),
+    Platform::new(PlatformMotion::Linear {
+      from,
+      to: Vec3::new(-20.0, 1.0, -20.0),
+      speed: 3.0,
+    }),
+    RigidBody::KinematicPositionBased,
+    Collider::cuboid(2.0, 0.25, 2.0),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(4.0, 0.5, 4.0)),
+      material: material.clone(),
+      transform: Transform::from_translation(from),
+      ..default()
+    },
+    TranslationInterpolation::new(from),
+  ));
+
+  // エレベーター
+  let from = Vec3::new(-30.0, 0.25, 0.0);
+  commands.spawn((
+    Name::new("Elevator"),
+    Platform::new(PlatformMotion::Linear {
+      from,
+      to: Vec3::new(-30.0, 8.0, 0.0),
+      speed: 2.0,
+    }),
+    RigidBody::KinematicPositionBased,
+    Collider::cuboid(2.0, 0.25, 2.0),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(4.0, 0.5, 4.0)),
+      material: material.clone(),
+      transform: Transform::from_translation(from),
+      ..default()
+    },
+    TranslationInterpolation::new(from),
+  ));
+
+  // 回転する円盤
+  commands.spawn((
+    Name::new("Rotating Disk"),
+    Platform::new(PlatformMotion::Rotating { angular_speed: 1.0 }),
+    RigidBody::KinematicPositionBased,
+    Collider::cylinder(0.25, 5.0),
+    PbrBundle {
+      mesh: meshes.add(Cylinder::new(5.0, 0.5)),
+      material: material.clone(),
+      transform: Transform::from_xyz(0.0, 0.25, 20.0),
+      ..default()
+    },
+  ));
+
+  // ルームランナー
+  commands.spawn((
+    Name::new("Conveyor"),
+    Platform::new(PlatformMotion::Conveyor {
+      velocity: Vec3::new(0.0, 0.0, 6.0),
+    }),
+    Collider::cuboid(1.5, 0.1, 8.0),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(3.0, 0.2, 16.0)),
+      material,
+      transform: Transform::from_xyz(15.0, 0.1, 20.0),
+      ..default()
+    },
+  ));
+}
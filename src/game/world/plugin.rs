@@ -1,13 +1,29 @@
 use bevy::prelude::*;
 
-use super::init_world;
+use super::{
+  generate_ladder, generate_trigger, init_ladders, init_platforms, init_water, init_world,
+  link_teleporter, update_buoyancy, update_platforms, BoostZone, GravityZone, JumpPad, Ladder,
+  Platform, Teleporter, Water,
+};
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
   fn build(&self, app: &mut App) {
     app
-      .add_systems(Startup, init_world)
-      .insert_resource(ClearColor(Color::srgb(0.52, 0.76, 0.88)));
+      .add_systems(
+        Startup,
+        (init_world, init_platforms, init_ladders, init_water),
+      )
+      .add_systems(FixedUpdate, (update_platforms, update_buoyancy))
+      .add_systems(Update, (generate_ladder, generate_trigger, link_teleporter))
+      .insert_resource(ClearColor(Color::srgb(0.52, 0.76, 0.88)))
+      .register_type::<BoostZone>()
+      .register_type::<GravityZone>()
+      .register_type::<JumpPad>()
+      .register_type::<Ladder>()
+      .register_type::<Platform>()
+      .register_type::<Teleporter>()
+      .register_type::<Water>();
   }
 }
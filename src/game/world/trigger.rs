@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+
+use super::insert_sensor_colliders;
+
+/// 入ったプレイヤーをvelocityで打ち上げる
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct JumpPad {
+  /// 打ち上げる速度(ジャンプ台の向きで回転する)
+  pub velocity: Vec3,
+}
+
+/// 中にいる間はプレイヤーの移動速度をmultiplier倍にする
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct BoostZone {
+  pub multiplier: f32,
+}
+
+/// 中にいる間はプレイヤーに掛かる重力の向きと強さを変える
+/// プレイヤーの上方向は重力の逆向きに滑らかに変わる
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct GravityZone {
+  /// 重力の向き(範囲の向きで回転する)
+  pub direction: Vec3,
+  /// 重力の強さの倍率
+  pub strength: f32,
+}
+
+/// 入ったプレイヤーをdestinationの位置に移動させる
+/// 入った向きと速度はdestinationの向きに合わせて回転する
+#[derive(Component, Reflect, Debug)]
+pub struct Teleporter {
+  pub destination: Entity,
+}
+
+/// glTFから読み込んだテレポーターの行き先の名前
+/// 同じ名前のエンティティが見つかったら`Teleporter`に置き換える
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct TeleporterLink(pub String);
+
+/// プレイヤーが入ると作動する範囲
+#[derive(Bundle)]
+pub struct TriggerBundle<T: Component> {
+  name: Name,
+  trigger: T,
+  sensor: Sensor,
+  collider: Collider,
+}
+
+impl<T: Component> TriggerBundle<T> {
+  pub fn new(name: &str, trigger: T, half_extents: Vec3) -> Self {
+    Self {
+      name: Name::new(name.to_string()),
+      trigger,
+      sensor: Sensor,
+      collider: Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+    }
+  }
+}
+
+#[derive(Deserialize, Debug)]
+struct SerDeTrigger {
+  /// ジャンプ台の打ち上げる速度([x, y, z])
+  jump_pad: Option<[f32; 3]>,
+  /// 加速する範囲の倍率
+  boost: Option<f32>,
+  /// テレポーターの行き先のノードの名前
+  teleporter: Option<String>,
+  /// 重力の向きと強さ([x, y, z]、長さが倍率)
+  gravity: Option<[f32; 3]>,
+}
+
+// カスタムプロパティ`jump_pad`、`boost`、`teleporter`、`gravity`を持つglTFのノードを作動する範囲にする
+pub(super) fn generate_trigger(
+  mut commands: Commands,
+  meshes: Res<Assets<Mesh>>,
+  query: Query<(&GltfExtras, &Children), Added<GltfExtras>>,
+  mesh_query: Query<&Handle<Mesh>, Without<Collider>>,
+) {
+  for (gltf_extras, children) in query.iter() {
+    let Ok(trigger) = serde_json::from_str::<SerDeTrigger>(&gltf_extras.value) else {
+      continue;
+    };
+
+    if let Some(velocity) = trigger.jump_pad {
+      let jump_pad = JumpPad {
+        velocity: Vec3::from_array(velocity),
+      };
+      insert_sensor_colliders(&mut commands, &meshes, children, &mesh_query, jump_pad);
+    }
+    if let Some(multiplier) = trigger.boost {
+      let boost_zone = BoostZone { multiplier };
+      insert_sensor_colliders(&mut commands, &meshes, children, &mesh_query, boost_zone);
+    }
+    if let Some(destination) = trigger.teleporter {
+      let link = TeleporterLink(destination);
+      insert_sensor_colliders(&mut commands, &meshes, children, &mesh_query, link);
+    }
+    if let Some(gravity) = trigger.gravity {
+      let gravity = Vec3::from_array(gravity);
+      let gravity_zone = GravityZone {
+        direction: gravity.try_normalize().unwrap_or(Vec3::NEG_Y),
+        strength: gravity.length(),
+      };
+      insert_sensor_colliders(&mut commands, &meshes, children, &mesh_query, gravity_zone);
+    }
+  }
+}
+
+pub(super) fn link_teleporter(
+  mut commands: Commands,
+  link_query: Query<(Entity, &TeleporterLink)>,
+  name_query: Query<(Entity, &Name)>,
+) {
+  for (entity, link) in link_query.iter() {
+    if let Some((destination, _)) = name_query.iter().find(|(_, name)| name.as_str() == link.0) {
+      commands
+        .entity(entity)
+        .remove::<TeleporterLink>()
+        .insert(Teleporter { destination });
+    }
+  }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::BoxBundle;
+
+/// 水の範囲(Sensorのコライダーと一緒に使う)
+#[derive(Component, Reflect, Debug)]
+pub struct Water {
+  /// エンティティの原点から水面までの高さ
+  pub surface_offset: f32,
+  /// 沈んでいる物体に掛かる浮力(重力の何倍か)
+  pub buoyancy: f32,
+  /// 沈んでいる物体の速度の減衰
+  pub linear_damping: f32,
+}
+
+impl Water {
+  pub fn new(surface_offset: f32) -> Self {
+    Self {
+      surface_offset,
+      buoyancy: 1.5,
+      linear_damping: 2.0,
+    }
+  }
+}
+
+/// pointを含む水と水面の高さを探す
+pub fn find_water<'a>(
+  rapier_context: &RapierContext,
+  water_query: &'a Query<(&Water, &GlobalTransform)>,
+  point: Vec3,
+) -> Option<(Entity, &'a Water, f32)> {
+  let mut found = None;
+  rapier_context.intersections_with_point(
+    point,
+    QueryFilter::default().exclude_solids(),
+    |entity| match water_query.get(entity) {
+      Ok((water, transform)) => {
+        found = Some((
+          entity,
+          water,
+          transform.translation().y + water.surface_offset,
+        ));
+        false
+      }
+      Err(_) => true,
+    },
+  );
+  found
+}
+
+// 水に沈んでいる動的な剛体に浮力と抵抗を加える
+pub(super) fn update_buoyancy(
+  rapier_context: Res<RapierContext>,
+  rapier_config: Res<RapierConfiguration>,
+  water_query: Query<(&Water, &GlobalTransform)>,
+  mut body_query: Query<(
+    &RigidBody,
+    &GlobalTransform,
+    &ReadMassProperties,
+    &mut ExternalForce,
+    &mut Damping,
+  )>,
+) {
+  for (rigid_body, transform, mass_properties, mut external_force, mut damping) in
+    body_query.iter_mut()
+  {
+    if *rigid_body != RigidBody::Dynamic {
+      continue;
+    }
+
+    let position = transform.translation();
+    let Some((_, water, surface)) = find_water(&rapier_context, &water_query, position) else {
+      external_force.force = Vec3::ZERO;
+      damping.linear_damping = 0.0;
+      continue;
+    };
+
+    // 水面の近くでは浮力を弱めて浮かせる
+    let submerged = (surface - position.y + 0.5).clamp(0.0, 1.0);
+    external_force.force =
+      -rapier_config.gravity * mass_properties.get().mass * water.buoyancy * submerged;
+    damping.linear_damping = water.linear_damping * submerged;
+  }
+}
+
+// 水のテストコース
+pub(super) fn init_water(
+  mut commands: Commands,
+  mut meshes: ResMut<Assets<Mesh>>,
+  mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+  const DEPTH: f32 = 4.0;
+
+  commands.spawn((
+    Name::new("Pool"),
+    Water::new(DEPTH / 2.0),
+    Sensor,
+    Collider::cuboid(8.0, DEPTH / 2.0, 8.0),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(16.0, DEPTH, 16.0)),
+      material: materials.add(StandardMaterial {
+        base_color: Color::srgba_u8(40, 120, 255, 120),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+      }),
+      transform: Transform::from_xyz(-20.0, DEPTH / 2.0, 30.0),
+      ..default()
+    },
+  ));
+
+  // 浮かぶ箱
+  commands.spawn((
+    BoxBundle::default(),
+    PbrBundle {
+      mesh: meshes.add(Cuboid::new(2.0, 2.0, 2.0)),
+      material: materials.add(Color::srgb_u8(255, 200, 0)),
+      transform: Transform::from_xyz(-20.0, 6.0, 30.0),
+      ..default()
+    },
+  ));
+}